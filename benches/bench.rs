@@ -1,6 +1,5 @@
 #![feature(test)]
 
-#[macro_use]
 extern crate bit_collection;
 extern crate test;
 
@@ -18,8 +17,8 @@ static ALL_BITS: [U8Bit; 8] = [
     U8Bit::_4, U8Bit::_5, U8Bit::_6, U8Bit::_7,
 ];
 
-#[bit(U8Bit)]
 #[derive(BitCollection)]
+#[bit(U8Bit)]
 struct U8Bits(u8);
 
 fn iter_with<T: BitCollection, U, F: FnMut(&mut T) -> Option<U>>(mut f: F) {
@@ -80,3 +79,90 @@ fn bench_match_quantity_100(b: &mut Bencher) {
         }
     });
 }
+
+#[bench]
+fn bench_unwrap_quantity_lsb_100(b: &mut Bencher) {
+    b.iter(|| {
+        for _ in 0..100 {
+            let bits = black_box(U8Bits::from(U8Bit::_5));
+            let item = match bits.quantity() {
+                Quantity::Single => Some(bits.lsb().unwrap()),
+                _ => None,
+            };
+            black_box(item);
+        }
+    });
+}
+
+#[bench]
+fn bench_match_quantity_item_100(b: &mut Bencher) {
+    b.iter(|| {
+        for _ in 0..100 {
+            let item = match black_box(U8Bits::from(U8Bit::_5)).quantity_item() {
+                QuantityItem::Single(item) => Some(item),
+                _ => None,
+            };
+            black_box(item);
+        }
+    });
+}
+
+const WORD: u64 = 0x8142_2418_1824_4281;
+
+#[bench]
+fn bench_pop_msb_from(b: &mut Bencher) {
+    b.iter(|| {
+        iter_with(|bits: &mut U8Bits| {
+            bits.msb().map(|x| {
+                bits.remove(x);
+                x
+            })
+        });
+    });
+}
+
+#[bench]
+fn bench_select(b: &mut Bencher) {
+    b.iter(|| {
+        for n in 0..16 {
+            black_box(black_box(WORD).select(n));
+        }
+    });
+}
+
+#[bench]
+fn bench_select_soft(b: &mut Bencher) {
+    b.iter(|| {
+        for n in 0..16 {
+            black_box(word::soft::select(black_box(WORD), n));
+        }
+    });
+}
+
+#[bench]
+fn bench_pdep(b: &mut Bencher) {
+    b.iter(|| {
+        black_box(black_box(0xffffu64).pdep(black_box(WORD)));
+    });
+}
+
+#[bench]
+fn bench_pdep_soft(b: &mut Bencher) {
+    b.iter(|| {
+        black_box(word::soft::pdep(black_box(0xffffu64), black_box(WORD)));
+    });
+}
+
+#[bench]
+fn bench_pext(b: &mut Bencher) {
+    b.iter(|| {
+        black_box(black_box(!0u64).pext(black_box(WORD)));
+    });
+}
+
+#[bench]
+fn bench_pext_soft(b: &mut Bencher) {
+    b.iter(|| {
+        black_box(word::soft::pext(black_box(!0u64), black_box(WORD)));
+    });
+}
@@ -5,17 +5,115 @@ extern crate quote;
 extern crate proc_macro;
 extern crate syn;
 
-use proc_macro::TokenStream;
+use proc_macro::{Delimiter, Group, Punct, Spacing, TokenStream, TokenTree};
 use syn::{Body, Lit, MetaItem, NestedMetaItem, Ty};
 use quote::Ident as Ident;
 
-#[proc_macro_derive(BitCollection, attributes(bit))]
+/// Words in `#[bit]` that enable options rather than name the item type.
+const OPTIONS: &[&str] = &[
+    "bitflags_compat", "enumset", "fixedbitset", "bytemuck", "zerocopy",
+];
+
+#[proc_macro_derive(BitCollection, attributes(bit, bit_field, bit_default))]
 pub fn bit_collection(input: TokenStream) -> TokenStream {
+    let (input, path) = take_crate_path(input);
     let ast = syn::parse_derive_input(&input.to_string()).unwrap();
-    impl_bit_collection(&ast).parse().unwrap()
+    let output = impl_bit_collection(&ast, path.is_none()).parse().unwrap();
+    match path {
+        Some(path) => replace_ident(output, "__bit_collection", &path),
+        None => output,
+    }
+}
+
+/// Removes `crate = path` from `#[bit(...)]` and returns the path.
+///
+/// This is done before parsing, since `syn` only sees the input as a string.
+/// The path may be given as tokens rather than a string so that a macro can
+/// pass `$crate`, which can't be written as a string.
+fn take_crate_path(input: TokenStream) -> (TokenStream, Option<TokenStream>) {
+    let mut path = None;
+    let input = input.into_iter().map(|tree| {
+        let attr = match tree {
+            TokenTree::Group(ref g) if g.delimiter() == Delimiter::Bracket => g.clone(),
+            _ => return tree,
+        };
+        let mut parts: Vec<TokenTree> = attr.stream().into_iter().collect();
+        // An attribute passed as `$attr:meta` arrives within an invisible group.
+        if let [TokenTree::Group(ref g)] = parts[..] {
+            if g.delimiter() == Delimiter::None {
+                parts = g.stream().into_iter().collect();
+            }
+        }
+        let list = match (parts.first(), parts.get(1)) {
+            (Some(&TokenTree::Ident(ref ident)), Some(&TokenTree::Group(ref list)))
+                if ident.to_string() == "bit" && list.delimiter() == Delimiter::Parenthesis
+                => list.clone(),
+            _ => return tree,
+        };
+
+        let mut entries: Vec<Vec<TokenTree>> = vec![vec![]];
+        for tree in list.stream() {
+            match tree {
+                TokenTree::Punct(ref p) if p.as_char() == ',' => entries.push(vec![]),
+                tree => entries.last_mut().unwrap().push(tree),
+            }
+        }
+        let mut kept = Vec::new();
+        for entry in entries.into_iter().filter(|e| !e.is_empty()) {
+            let is_crate = match (entry.first(), entry.get(1)) {
+                (Some(&TokenTree::Ident(ref key)), Some(&TokenTree::Punct(ref eq))) => {
+                    key.to_string() == "crate" && eq.as_char() == '='
+                },
+                _ => false,
+            };
+            if !is_crate {
+                kept.push(entry);
+                continue;
+            }
+            let value = &entry[2..];
+            let string = match value {
+                [TokenTree::Literal(lit)] => Some(lit.to_string()),
+                _ => None,
+            };
+            path = Some(match string {
+                Some(ref s) if s.len() >= 2 && s.starts_with('"') && s.ends_with('"') => {
+                    s[1..s.len() - 1].parse().expect("Expected a path in `crate = \"...\"`.")
+                },
+                _ => value.iter().cloned().collect(),
+            });
+        }
+
+        let mut stream = Vec::new();
+        for (i, entry) in kept.into_iter().enumerate() {
+            if i != 0 {
+                stream.push(TokenTree::Punct(Punct::new(',', Spacing::Alone)));
+            }
+            stream.extend(entry);
+        }
+        let mut list = Group::new(Delimiter::Parenthesis, stream.into_iter().collect());
+        list.set_span(parts[1].span());
+        parts[1] = TokenTree::Group(list);
+        let mut attr_group = Group::new(Delimiter::Bracket, parts.into_iter().collect());
+        attr_group.set_span(attr.span());
+        TokenTree::Group(attr_group)
+    }).collect();
+    (input, path)
+}
+
+/// Replaces each `ident` in `tokens`, including within groups, with `with`.
+fn replace_ident(tokens: TokenStream, ident: &str, with: &TokenStream) -> TokenStream {
+    tokens.into_iter().map(|tree| match tree {
+        TokenTree::Ident(ref i) if i.to_string() == ident => with.clone(),
+        TokenTree::Group(ref g) => {
+            let mut group = Group::new(g.delimiter(), replace_ident(g.stream(), ident, with));
+            group.set_span(g.span());
+            TokenTree::Group(group).into()
+        },
+        tree => tree.into(),
+    }).collect()
 }
 
-fn impl_bit_collection(ast: &syn::DeriveInput) -> quote::Tokens {
+fn impl_bit_collection(ast: &syn::DeriveInput, link: bool) -> quote::Tokens {
     let std: Ident = if cfg!(feature = "std") {
         "std".into()
     } else {
@@ -31,13 +129,52 @@ fn impl_bit_collection(ast: &syn::DeriveInput) -> quote::Tokens {
         None
     }).next().expect("No `#[bit]` attribute found.");
 
-    let item = bit_list.iter().filter_map(|x| {
-        if let NestedMetaItem::MetaItem(MetaItem::Word(ref ident)) = *x {
-            Some(Ident::from(ident.as_ref()))
-        } else {
-            None
+    let has_word = |x: &str| bit_list.iter().any(|a| {
+        *a == NestedMetaItem::MetaItem(MetaItem::Word(x.into()))
+    });
+
+    // The two collection types of `#[bit(product(A, B))]`, whose items make
+    // up the item of this collection
+    let product = bit_list.iter().filter_map(|a| {
+        if let NestedMetaItem::MetaItem(MetaItem::List(ref ident, ref list)) = *a {
+            if ident == "product" {
+                return Some(list);
+            }
+        }
+        None
+    }).next().map(|list| {
+        let types: Vec<Ident> = list.iter().map(|t| match *t {
+            NestedMetaItem::MetaItem(MetaItem::Word(ref ident)) => Ident::from(ident.as_ref()),
+            NestedMetaItem::Literal(Lit::Str(ref s, _)) => Ident::from(s.as_ref()),
+            _ => panic!("Expected a collection type in `product(...)`, found {:?}", t),
+        }).collect();
+        if types.len() != 2 {
+            panic!("Expected two collection types in `product(...)`.");
+        }
+        (types[0].clone(), types[1].clone())
+    });
+
+    // The item may be a string to allow for generic types: `#[bit("Id<T>")]`
+    let item = if let Some((ref a, ref b)) = product {
+        Ident::from(format!(
+            "(<{a} as ::{std}::iter::IntoIterator>::Item, <{b} as ::{std}::iter::IntoIterator>::Item)",
+            a = a, b = b, std = std,
+        ))
+    } else { bit_list.iter().filter_map(|x| {
+        match *x {
+            NestedMetaItem::MetaItem(MetaItem::Word(ref ident)) => {
+                if OPTIONS.contains(&ident.as_ref()) {
+                    None
+                } else {
+                    Some(Ident::from(ident.as_ref()))
+                }
+            },
+            NestedMetaItem::Literal(Lit::Str(ref s, _)) => {
+                Some(Ident::from(s.as_ref()))
+            },
+            _ => None,
         }
-    }).next().expect("No bit item found: `#[bit(Item)]`.");
+    }).next().expect("No bit item found: `#[bit(Item)]`.") };
 
     let get_attr = |x: &str| {
         bit_list.iter().filter_map(|a| {
@@ -52,52 +189,116 @@ fn impl_bit_collection(ast: &syn::DeriveInput) -> quote::Tokens {
         }).next()
     };
 
-    let zero = ::syn::Ident::new("0");
     let name = Ident::from(ast.ident.as_ref());
-    let mask = get_attr("mask").unwrap_or_else(|| "!0".into());
-    let iter = get_attr("iter").unwrap_or_else(|| "BitIter".into());
+    let mask = if product.is_some() {
+        if get_attr("mask").is_some() {
+            panic!("The `product` option does not allow a `mask`.");
+        }
+        if !ast.generics.ty_params.is_empty() || !ast.generics.lifetimes.is_empty() {
+            panic!("The `product` option does not support generic types.");
+        }
+        "Self::__MASK".into()
+    } else {
+        get_attr("mask").unwrap_or_else(|| "!0".into())
+    };
+    let iter = get_attr("iter").unwrap_or_else(|| "__bit_collection::BitIter".into());
     let backing: Ident;
+    let only_markers: bool;
+    let with_bits: quote::Tokens;
+    let cloned: Vec<quote::Tokens>;
+
+    // Generated impls that take their own generic parameters put them after
+    // those of the type. Defaults are not allowed in impls.
+    let mut generics = ast.generics.clone();
+    for param in &mut generics.ty_params {
+        param.default = None;
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let lifetimes = &generics.lifetimes;
+    let ty_params = &generics.ty_params;
+    let params = quote!(#(#lifetimes,)* #(#ty_params,)*);
+    let ty = quote!(#name #ty_generics);
 
     let (bits, from_x, from_x_masked, full, empty) = if let Body::Struct(ref data) = ast.body {
-        let field = data.fields().get(0).expect("No fields found.");
+        let fields = data.fields();
+        if fields.is_empty() {
+            panic!("No fields found.");
+        }
 
-        // Extract inner type that may be surrounded by parentheses
-        let extract_ty = || {
-            let mut ty = &field.ty;
-            loop {
-                match *ty {
-                    Ty::Paren(ref b) => ty = &b,
-                    Ty::Path(_, ref p) => return p,
-                    _ => panic!("Incompatible type: {:?}", ty),
-                }
-            }
-        };
-        backing = extract_ty()
+        // The bits are held by the `#[bit_field]` or else the first field
+        let index = fields.iter().position(|f| {
+            f.attrs.iter().any(|a| a.value == MetaItem::Word("bit_field".into()))
+        }).unwrap_or(0);
+        let field = &fields[index];
+
+        backing = extract_path(&field.ty)
             .segments.get(0).expect("No backing type found.")
             .ident.as_ref().into();
 
-        let masked_x = quote!(x & #mask);
+        let field_ident = |i: usize, f: &syn::Field| {
+            f.ident.clone().unwrap_or_else(|| syn::Ident::new(i.to_string()))
+        };
 
-        if let Some(ref bits) = field.ident {
-            let from        = quote!(#name{#bits: x});
-            let from_masked = quote!(#name{#bits: #masked_x});
-            let full        = quote!(#name{#bits: #mask});
-            let empty       = quote!(#name{#bits: 0});
-            (bits, from, from_masked, full, empty)
-        } else {
-            (
-                &zero,
-                quote!(#name(x)),
-                quote!(#name(#masked_x)),
-                quote!(#name(#mask)),
-                quote!(#name(0))
-            )
+        // Every other field takes its default value, or is copied from an
+        // existing collection by `with_bits`
+        let mut others = Vec::new();
+        let mut carried = Vec::new();
+        for (i, f) in fields.iter().enumerate().filter(|&(i, _)| i != index) {
+            let ident = field_ident(i, f);
+            let value = match default_value(f, &std) {
+                Some(value) => value,
+                None => {
+                    // `Default::default()` can't be called in `FULL` and `EMPTY`
+                    let ty = &f.ty;
+                    let message = format!(
+                        "Field `{}` of type `{}` has no constant default value. Give it \
+                         one with `#[bit_default = \"...\"]`.",
+                        ident, quote!(#ty),
+                    );
+                    return quote!(compile_error!(#message););
+                },
+            };
+            others.push(quote!(#ident: #value,));
+            carried.push(quote!(#ident: ::#std::clone::Clone::clone(&self.#ident),));
         }
+        let others = &others;
+        let bits = field_ident(index, field);
+
+        with_bits = if carried.is_empty() {
+            quote!()
+        } else {
+            quote! {
+                #[inline]
+                unsafe fn with_bits(&self, x: #backing) -> Self {
+                    #name { #bits: x, #(#carried)* }
+                }
+            }
+        };
+
+        only_markers = fields.iter().enumerate().all(|(i, f)| {
+            i == index || type_name(&f.ty) == Some("PhantomData")
+        });
+
+        cloned = fields.iter().enumerate().map(|(i, f)| {
+            let ident = field_ident(i, f);
+            quote!(#ident: ::#std::clone::Clone::clone(&self.#ident),)
+        }).collect();
+
+        let new = |x: quote::Tokens| quote! {
+            #name { #bits: #x, #(#others)* }
+        };
+        let from        = new(quote!(x));
+        let from_masked = new(quote!(x & #mask));
+        let full        = new(quote!(#mask));
+        let empty       = new(quote!(0));
+        (bits, from, from_masked, full, empty)
     } else {
         panic!("Expected struct type.");
     };
 
-    let item_from_raw = quote! {
+    let item_from_raw = if product.is_some() {
+        quote!(Self::__item(raw as u32))
+    } else { quote! {
         // Endian agnostic code integer to item conversion
         use #std::mem::transmute_copy;
         match ::#std::mem::size_of::<#item>() {
@@ -107,18 +308,586 @@ fn impl_bit_collection(ast: &syn::DeriveInput) -> quote::Tokens {
             8 => transmute_copy(&(raw as u64)),
             _ => unreachable!(),
         }
-    };
+    } };
 
-    let convert_x = if let Some(retr) = get_attr("retr") {
+    let convert_x = if product.is_some() {
+        quote!(Self::__index(x))
+    } else if let Some(retr) = get_attr("retr") {
         quote!(x.#retr)
     } else {
         quote!(x as #backing)
     };
 
-    quote! {
-        impl From<#item> for #name {
+    let bitflags_compat = if has_word("bitflags_compat") {
+        quote! {
+            /// Methods with the same signatures as those generated by
+            /// `bitflags`.
+            #[allow(dead_code)]
+            impl #impl_generics #ty #where_clause {
+                /// Returns the raw value of the bits.
+                #[inline]
+                pub const fn bits(&self) -> #backing {
+                    self.#bits
+                }
+
+                /// Converts from raw bits, returning `None` if any bit does
+                /// not correspond to an item.
+                #[inline]
+                pub const fn from_bits(x: #backing) -> Option<Self> {
+                    if x & !#mask == 0 {
+                        Some(#from_x)
+                    } else {
+                        None
+                    }
+                }
+
+                /// Converts from raw bits, removing any bit that does not
+                /// correspond to an item.
+                #[inline]
+                pub const fn from_bits_truncate(x: #backing) -> Self {
+                    #from_x_masked
+                }
+
+                /// Returns a collection of all items.
+                #[inline]
+                pub const fn all() -> Self {
+                    #full
+                }
+
+                /// Returns an empty collection.
+                #[inline]
+                pub const fn empty() -> Self {
+                    #empty
+                }
+
+                /// Returns whether all items are in `self`.
+                #[inline]
+                pub const fn is_all(&self) -> bool {
+                    self.#bits == #mask
+                }
+
+                /// Returns whether no items are in `self`.
+                #[inline]
+                pub const fn is_empty(&self) -> bool {
+                    self.#bits == 0
+                }
+
+                /// Returns whether all items of `other` are in `self`.
+                #[inline]
+                pub fn contains<__T: Into<Self>>(&self, other: __T) -> bool {
+                    __bit_collection::BitCollection::contains(self, other)
+                }
+
+                /// Returns whether any items of `other` are in `self`.
+                #[inline]
+                pub fn intersects<__T: Into<Self>>(&self, other: __T) -> bool {
+                    self.#bits & other.into().#bits != 0
+                }
+
+                /// Inserts the items of `other`.
+                #[inline]
+                pub fn insert<__T: Into<Self>>(&mut self, other: __T) {
+                    self.#bits |= other.into().#bits;
+                }
+
+                /// Removes the items of `other`.
+                #[inline]
+                pub fn remove<__T: Into<Self>>(&mut self, other: __T) {
+                    self.#bits &= !other.into().#bits;
+                }
+
+                /// Toggles the items of `other`.
+                #[inline]
+                pub fn toggle<__T: Into<Self>>(&mut self, other: __T) {
+                    self.#bits ^= other.into().#bits;
+                }
+
+                /// Inserts or removes the items of `other` depending on
+                /// `value`.
+                #[inline]
+                pub fn set<__T: Into<Self>>(&mut self, other: __T, value: bool) {
+                    if value {
+                        self.insert(other);
+                    } else {
+                        self.remove(other);
+                    }
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let enumset = if has_word("enumset") {
+        quote! {
+            impl #impl_generics From<#ty> for ::enumset::EnumSet<#item> #where_clause {
+                #[inline]
+                fn from(collection: #ty) -> Self {
+                    __bit_collection::BitCollection::into_enum_set(collection)
+                }
+            }
+
+            impl #impl_generics ::#std::convert::TryFrom<::enumset::EnumSet<#item>> for #ty
+                #where_clause
+            {
+                type Error = ::enumset::EnumSet<#item>;
+
+                #[inline]
+                fn try_from(set: ::enumset::EnumSet<#item>) -> Result<Self, Self::Error> {
+                    <Self as __bit_collection::BitCollection>::from_enum_set(set).ok_or(set)
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let fixedbitset = if has_word("fixedbitset") {
+        quote! {
+            impl #impl_generics From<#ty> for ::fixedbitset::FixedBitSet #where_clause {
+                #[inline]
+                fn from(collection: #ty) -> Self {
+                    __bit_collection::BitCollection::to_fixed_bit_set(&collection)
+                }
+            }
+
+            impl #impl_generics ::#std::convert::TryFrom<::fixedbitset::FixedBitSet> for #ty
+                #where_clause
+            {
+                type Error = ::fixedbitset::FixedBitSet;
+
+                #[inline]
+                fn try_from(set: ::fixedbitset::FixedBitSet) -> Result<Self, Self::Error> {
+                    match <Self as __bit_collection::BitCollection>::from_fixed_bit_set(&set) {
+                        Some(collection) => Ok(collection),
+                        None => Err(set),
+                    }
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    // Any bytes must make a valid collection, so only the bits may hold data
+    // and every bit must correspond to an item.
+    let pod = ["bytemuck", "zerocopy"].iter().filter(|x| has_word(x)).map(|x| {
+        let transparent = ast.attrs.iter().any(|a| {
+            a.value == MetaItem::List("repr".into(), vec![
+                NestedMetaItem::MetaItem(MetaItem::Word("transparent".into())),
+            ])
+        });
+        if !transparent {
+            panic!("The `{}` option requires `#[repr(transparent)]`.", x);
+        }
+        if !ast.generics.ty_params.is_empty() || !ast.generics.lifetimes.is_empty() {
+            panic!("The `{}` option does not support generic types.", x);
+        }
+        if !only_markers {
+            panic!("The `{}` option requires other fields to be `PhantomData`.", x);
+        }
+        let message = format!("The `{}` option requires `FULL` to be `!0`.", x);
+        quote! {
+            const _: () = assert!(<#ty as __bit_collection::BitCollection>::FULL.#bits == !0, #message);
+        }
+    }).collect::<Vec<_>>();
+
+    let bytemuck = if has_word("bytemuck") {
+        quote! {
+            unsafe impl ::bytemuck::Zeroable for #ty {}
+
+            unsafe impl ::bytemuck::Pod for #ty {}
+        }
+    } else {
+        quote!()
+    };
+
+    // Bit `i * WIDTH + j` of a product is the pair of the items of bit `i` of
+    // `A` and bit `j` of `B`, so each row of `WIDTH` bits is a `B`. Bits past
+    // the last item of either are left out.
+    let product_impl = if let Some((ref a, ref b)) = product {
+        let item_of = |c: &Ident| Ident::from(format!(
+            "<{} as ::{}::iter::IntoIterator>::Item", c, std,
+        ));
+        let (a_item, b_item) = (item_of(a), item_of(b));
+        let size = quote!((::#std::mem::size_of::<#backing>() * 8) as u32);
+        let collection = quote!(__bit_collection::BitCollection);
+        quote! {
+            #[allow(dead_code)]
+            impl #ty {
+                /// Returns the low bits of `bytes` in little-endian order.
+                const fn __widen(bytes: &[u8]) -> u128 {
+                    let mut x = 0;
+                    let mut i = 0;
+                    while i < bytes.len() {
+                        x |= (bytes[i] as u128) << (i * 8);
+                        i += 1;
+                    }
+                    x
+                }
+
+                /// The bits of `A` up to its last item, which is the number of
+                /// rows.
+                const __ROWS: u32 = {
+                    let full = <#a as #collection>::FULL;
+                    128 - Self::__widen(&full.to_le_bytes()).leading_zeros()
+                };
+
+                /// The bits of `B` up to its last item, which is the width of
+                /// a row.
+                const __WIDTH: u32 = {
+                    let full = <#b as #collection>::FULL;
+                    128 - Self::__widen(&full.to_le_bytes()).leading_zeros()
+                };
+
+                /// The bits of a row.
+                const __ROW: #backing = {
+                    assert!(
+                        Self::__ROWS * Self::__WIDTH <= #size,
+                        "The `product` option requires as many bits as `A` \
+                         times `B`.",
+                    );
+                    !0 >> (#size - Self::__WIDTH)
+                };
+
+                /// The first bit of each row.
+                const __COLUMN: #backing = {
+                    let mut column = 0;
+                    let mut i = 0;
+                    while i < Self::__ROWS {
+                        column |= 1 << (i * Self::__WIDTH);
+                        i += 1;
+                    }
+                    column
+                };
+
+                /// The bits of `B::FULL` in each row of `A::FULL`.
+                const __MASK: #backing = {
+                    let a = <#a as #collection>::FULL;
+                    let b = <#b as #collection>::FULL;
+                    let rows = Self::__widen(&a.to_le_bytes());
+                    let row = Self::__widen(&b.to_le_bytes()) as #backing;
+
+                    let mut mask = 0;
+                    let mut i = 0;
+                    while i < Self::__ROWS {
+                        if rows >> i & 1 != 0 {
+                            mask |= row << (i * Self::__WIDTH);
+                        }
+                        i += 1;
+                    }
+                    mask
+                };
+
+                #[inline]
+                fn __row_index(a: #a_item) -> u32 {
+                    let a = <#a as From<#a_item>>::from(a);
+                    <#a as #collection>::bits(&a).trailing_zeros()
+                }
+
+                #[inline]
+                fn __column_index(b: #b_item) -> u32 {
+                    let b = <#b as From<#b_item>>::from(b);
+                    <#b as #collection>::bits(&b).trailing_zeros()
+                }
+
+                #[inline]
+                fn __index((a, b): #item) -> u32 {
+                    let row = Self::__row_index(a) * Self::__WIDTH;
+                    row + Self::__column_index(b)
+                }
+
+                #[inline]
+                unsafe fn __item(raw: u32) -> #item {
+                    let row = raw / Self::__WIDTH;
+                    let column = raw % Self::__WIDTH;
+                    let a: #a = #collection::from_bits_unchecked(1 << row);
+                    let b: #b = #collection::from_bits_unchecked(1 << column);
+                    (
+                        #collection::lsb_unchecked(&a),
+                        #collection::lsb_unchecked(&b),
+                    )
+                }
+
+                /// Returns the items paired with `a`.
+                #[inline]
+                pub fn row(&self, a: #a_item) -> #b {
+                    let shift = Self::__row_index(a) * Self::__WIDTH;
+                    let row = (self.#bits >> shift) & Self::__ROW;
+                    unsafe { #collection::from_bits_unchecked(row as _) }
+                }
+
+                /// Returns the items paired with `b`.
+                #[inline]
+                pub fn column(&self, b: #b_item) -> #a {
+                    let bits = self.#bits >> Self::__column_index(b);
+                    let mask = Self::__COLUMN;
+                    let column = __bit_collection::Word::pext(bits, mask);
+                    unsafe { #collection::from_bits_unchecked(column as _) }
+                }
+
+                /// Creates a collection from rows, each paired with an item.
+                #[inline]
+                pub fn from_rows<__I>(rows: __I) -> Self
+                    where __I: IntoIterator<Item=(#a_item, #b)>
+                {
+                    let empty = <Self as #collection>::EMPTY;
+                    rows.into_iter().fold(empty, |mut acc, (a, row)| {
+                        let row = #collection::bits(&row);
+                        let row = <#backing as From<_>>::from(row);
+                        let shift = Self::__row_index(a) * Self::__WIDTH;
+                        acc.#bits |= row << shift;
+                        acc
+                    })
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    // Projections onto other collections with `#[bit(project(Other = "f"))]`
+    let project = bit_list.iter().filter_map(|a| {
+        if let NestedMetaItem::MetaItem(MetaItem::List(ref ident, ref list)) = *a {
+            if ident == "project" {
+                return Some(list);
+            }
+        }
+        None
+    }).flat_map(|list| list.iter()).map(|p| {
+        let (other, f) = match *p {
+            NestedMetaItem::MetaItem(MetaItem::NameValue(ref ident, Lit::Str(ref f, _))) => {
+                (Ident::from(ident.as_ref()), Ident::from(f.as_ref()))
+            },
+            _ => panic!("Expected `Other = \"f\"` in `project(...)`, found {:?}", p),
+        };
+        if !ast.generics.ty_params.is_empty() || !ast.generics.lifetimes.is_empty() {
+            panic!("The `project` option does not support generic types.");
+        }
+
+        // The cache holds the bits that the item of each bit maps to, limited
+        // to the items of `#other`. The function isn't `const`, so it's built
+        // on first use, which needs `std`.
+        let collection = quote!(__bit_collection::BitCollection);
+        let body = if cfg!(feature = "std") {
+            quote! {
+                const LEN: usize = ::std::mem::size_of::<#backing>() * 8;
+                type Bits = <#other as #collection>::Bits;
+                type Cache = ::std::sync::OnceLock<[Bits; LEN]>;
+                static CACHE: Cache = Cache::new();
+
+                let empty = <#other as #collection>::EMPTY;
+                let empty = #collection::bits(&empty);
+                let full = <#other as #collection>::FULL;
+                let full = #collection::bits(&full);
+                let cache = CACHE.get_or_init(|| {
+                    let mut cache = [empty; LEN];
+                    let mut bits = <Self as #collection>::FULL.#bits;
+                    while bits != 0 {
+                        let bit = bits & bits.wrapping_neg();
+                        bits ^= bit;
+                        let item = unsafe {
+                            let single: Self = #collection::from_bits_unchecked(bit);
+                            #collection::lsb_unchecked(&single)
+                        };
+                        let other = <#other as From<_>>::from(#f(item));
+                        let index = bit.trailing_zeros() as usize;
+                        cache[index] = #collection::bits(&other) & full;
+                    }
+                    cache
+                });
+
+                let mut bits = self.#bits;
+                let mut result = empty;
+                while bits != 0 {
+                    result |= cache[bits.trailing_zeros() as usize];
+                    bits &= bits.wrapping_sub(1);
+                }
+                // Every bit in the cache is within `#other::FULL`.
+                unsafe { #collection::from_bits_unchecked(result) }
+            }
+        } else {
+            quote! {
+                let other: #other = #collection::map_into(self, #f);
+                let full = <#other as #collection>::FULL;
+                let bits = #collection::bits(&other) & #collection::bits(&full);
+                // The bits are limited to `#other::FULL`.
+                unsafe { #collection::from_bits_unchecked(bits) }
+            }
+        };
+        quote! {
+            impl __bit_collection::Project<#other> for #ty {
+                #[inline]
+                fn project(self) -> #other {
+                    #body
+                }
+            }
+        }
+    }).collect::<Vec<_>>();
+
+    // Traits implemented with `#[bit(traits(...))]`, along with those they
+    // require
+    let traits: Vec<&str> = bit_list.iter().filter_map(|a| {
+        if let NestedMetaItem::MetaItem(MetaItem::List(ref ident, ref list)) = *a {
+            if ident == "traits" {
+                return Some(list);
+            }
+        }
+        None
+    }).flat_map(|list| list.iter().map(|t| {
+        if let NestedMetaItem::MetaItem(MetaItem::Word(ref ident)) = *t {
+            ident.as_ref()
+        } else {
+            panic!("Expected a trait name in `traits(...)`, found {:?}", t);
+        }
+    })).collect();
+    let has_trait = |x: &str| traits.iter().any(|t| {
+        *t == x || match x {
+            "Clone" => *t == "Copy",
+            "PartialEq" => *t == "Eq",
+            "PartialOrd" => *t == "Ord",
+            _ => false,
+        }
+    });
+    for t in &traits {
+        let supported = ["Default", "Clone", "Copy", "PartialEq", "Eq", "Hash",
+                         "PartialOrd", "Ord"];
+        if !supported.contains(t) {
+            panic!("Unsupported trait in `traits(...)`: {}", t);
+        }
+    }
+
+    let default_impl = if has_trait("Default") {
+        quote! {
+            impl #impl_generics ::#std::default::Default for #ty #where_clause {
+                #[inline]
+                fn default() -> Self {
+                    <Self as __bit_collection::BitCollection>::EMPTY
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let clone_impl = if has_trait("Clone") {
+        let cloned = &cloned;
+        quote! {
+            impl #impl_generics ::#std::clone::Clone for #ty #where_clause {
+                #[inline]
+                fn clone(&self) -> Self {
+                    #name { #(#cloned)* }
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let copy_impl = if has_trait("Copy") {
+        quote! {
+            impl #impl_generics ::#std::marker::Copy for #ty #where_clause {}
+        }
+    } else {
+        quote!()
+    };
+
+    let partial_eq_impl = if has_trait("PartialEq") {
+        quote! {
+            impl #impl_generics ::#std::cmp::PartialEq for #ty #where_clause {
+                #[inline]
+                fn eq(&self, other: &Self) -> bool {
+                    self.#bits == other.#bits
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let eq_impl = if has_trait("Eq") {
+        quote! {
+            impl #impl_generics ::#std::cmp::Eq for #ty #where_clause {}
+        }
+    } else {
+        quote!()
+    };
+
+    let hash_impl = if has_trait("Hash") {
+        quote! {
+            impl #impl_generics ::#std::hash::Hash for #ty #where_clause {
+                #[inline]
+                fn hash<__H: ::#std::hash::Hasher>(&self, state: &mut __H) {
+                    ::#std::hash::Hash::hash(&self.#bits, state)
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let partial_ord_impl = if has_trait("PartialOrd") {
+        quote! {
+            impl #impl_generics ::#std::cmp::PartialOrd for #ty #where_clause {
+                #[inline]
+                fn partial_cmp(&self, other: &Self) -> Option<::#std::cmp::Ordering> {
+                    use #std::cmp::Ordering::*;
+
+                    // Items are ordered lexicographically, as iterated over.
+                    // The sequences differ first at the lowest differing bit,
+                    // and the one with that item is less if the other has an
+                    // item after it.
+                    let diff = self.#bits ^ other.#bits;
+                    if diff == 0 {
+                        return Some(Equal);
+                    }
+                    let low = diff & diff.wrapping_neg();
+                    let above = !(low | low.wrapping_sub(1));
+                    Some(if self.#bits & low != 0 {
+                        if other.#bits & above != 0 { Less } else { Greater }
+                    } else if self.#bits & above != 0 {
+                        Greater
+                    } else {
+                        Less
+                    })
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    let ord_impl = if has_trait("Ord") {
+        quote! {
+            impl #impl_generics ::#std::cmp::Ord for #ty #where_clause {
+                #[inline]
+                fn cmp(&self, other: &Self) -> ::#std::cmp::Ordering {
+                    match ::#std::cmp::PartialOrd::partial_cmp(self, other) {
+                        Some(ordering) => ordering,
+                        None => unreachable!(),
+                    }
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    // This crate is linked under a private name within an anonymous constant,
+    // and its items are named by path, so callers don't need any of them in
+    // scope. With `crate = path`, that name is replaced by `path` instead.
+    let link = if link {
+        quote!(extern crate bit_collection as __bit_collection;)
+    } else {
+        quote!()
+    };
+
+    quote! { const _: () = {
+        #link
+
+        impl #impl_generics From<#item> for #ty #where_clause {
             #[inline(always)]
-            fn from(item: #item) -> #name {
+            fn from(item: #item) -> Self {
                 const ONE: #backing = 1;
                 let x = item;
                 let x = ONE << #convert_x;
@@ -126,128 +895,132 @@ fn impl_bit_collection(ast: &syn::DeriveInput) -> quote::Tokens {
             }
         }
 
-        impl From<#iter<#name>> for #name {
+        impl #impl_generics From<#iter<#ty>> for #ty #where_clause {
             #[inline(always)]
-            fn from(iter: #iter<#name>) -> #name {
+            fn from(iter: #iter<#ty>) -> Self {
                 iter.0
             }
         }
 
-        impl From<#backing> for #name {
+        impl #impl_generics From<#backing> for #ty #where_clause {
             #[inline(always)]
-            fn from(inner: #backing) -> #name {
+            fn from(inner: #backing) -> Self {
                 let x = inner;
                 #from_x_masked
             }
         }
 
-        impl<'a, T: Clone + Into<#name>> From<&'a T> for #name {
+        impl<'__a, #params __T: Clone + Into<#ty>> From<&'__a __T> for #ty #where_clause {
             #[inline]
-            fn from(r: &'a T) -> #name {
+            fn from(r: &'__a __T) -> Self {
                 r.clone().into()
             }
         }
 
-        impl<'a, T: Clone + Into<#name>> From<&'a mut T> for #name {
+        impl<'__a, #params __T: Clone + Into<#ty>> From<&'__a mut __T> for #ty #where_clause {
             #[inline]
-            fn from(r: &'a mut T) -> #name {
+            fn from(r: &'__a mut __T) -> Self {
                 r.clone().into()
             }
         }
 
-        impl<T: Into<#name>> ::#std::ops::BitAnd<T> for #name {
+        impl<#params __T: Into<#ty>> ::#std::ops::BitAnd<__T> for #ty #where_clause {
             type Output = Self;
 
             #[inline]
-            fn bitand(self, rhs: T) -> Self {
-                let x = self.#bits.bitand(rhs.into().#bits);
-                #from_x
+            fn bitand(mut self, rhs: __T) -> Self {
+                self.#bits &= rhs.into().#bits;
+                self
             }
         }
 
-        impl<T: Into<#name>> ::#std::ops::BitAndAssign<T> for #name {
+        impl<#params __T: Into<#ty>> ::#std::ops::BitAndAssign<__T> for #ty #where_clause {
             #[inline]
-            fn bitand_assign(&mut self, rhs: T) {
+            fn bitand_assign(&mut self, rhs: __T) {
                 self.#bits.bitand_assign(rhs.into().#bits);
             }
         }
 
-        impl<T: Into<#name>> ::#std::ops::BitOr<T> for #name {
+        impl<#params __T: Into<#ty>> ::#std::ops::BitOr<__T> for #ty #where_clause {
             type Output = Self;
 
             #[inline]
-            fn bitor(self, rhs: T) -> Self {
-                let x = self.#bits.bitor(rhs.into().#bits);
-                #from_x
+            fn bitor(mut self, rhs: __T) -> Self {
+                self.#bits |= rhs.into().#bits;
+                self
             }
         }
 
-        impl<T: Into<#name>> ::#std::ops::BitOrAssign<T> for #name {
+        impl<#params __T: Into<#ty>> ::#std::ops::BitOrAssign<__T> for #ty #where_clause {
             #[inline]
-            fn bitor_assign(&mut self, rhs: T) {
+            fn bitor_assign(&mut self, rhs: __T) {
                 self.#bits.bitor_assign(rhs.into().#bits);
             }
         }
 
-        impl<T: Into<#name>> ::#std::ops::BitXor<T> for #name {
+        impl<#params __T: Into<#ty>> ::#std::ops::BitXor<__T> for #ty #where_clause {
             type Output = Self;
 
             #[inline]
-            fn bitxor(self, rhs: T) -> Self {
-                let x = self.#bits.bitxor(rhs.into().#bits);
-                #from_x
+            fn bitxor(mut self, rhs: __T) -> Self {
+                self.#bits ^= rhs.into().#bits;
+                self
             }
         }
 
-        impl<T: Into<#name>> ::#std::ops::BitXorAssign<T> for #name {
+        impl<#params __T: Into<#ty>> ::#std::ops::BitXorAssign<__T> for #ty #where_clause {
             #[inline]
-            fn bitxor_assign(&mut self, rhs: T) {
+            fn bitxor_assign(&mut self, rhs: __T) {
                 self.#bits.bitxor_assign(rhs.into().#bits);
             }
         }
 
-        impl<T: Into<#name>> ::#std::ops::Sub<T> for #name {
+        impl<#params __T: Into<#ty>> ::#std::ops::Sub<__T> for #ty #where_clause {
             type Output = Self;
 
             #[inline]
-            fn sub(self, rhs: T) -> Self {
-                let x = self.#bits & !rhs.into().#bits;
-                #from_x
+            fn sub(mut self, rhs: __T) -> Self {
+                self.#bits &= !rhs.into().#bits;
+                self
             }
         }
 
-        impl<T: Into<#name>> ::#std::ops::SubAssign<T> for #name {
+        impl<#params __T: Into<#ty>> ::#std::ops::SubAssign<__T> for #ty #where_clause {
             #[inline]
-            fn sub_assign(&mut self, rhs: T) {
+            fn sub_assign(&mut self, rhs: __T) {
                 self.#bits &= !rhs.into().#bits;
             }
         }
 
-        impl ::#std::ops::Not for #name {
+        impl #impl_generics ::#std::ops::Not for #ty #where_clause {
             type Output = Self;
 
             #[inline]
-            fn not(self) -> Self {
-                (!self.#bits).into()
+            fn not(mut self) -> Self {
+                self.#bits = !self.#bits & #mask;
+                self
             }
         }
 
-        impl<T: Into<#name>> ::#std::iter::FromIterator<T> for #name {
+        impl<#params __T: Into<#ty>> ::#std::iter::FromIterator<__T> for #ty #where_clause {
             #[inline]
-            fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
-                iter.into_iter().fold(Self::EMPTY, BitCollection::inserting)
+            fn from_iter<__I: IntoIterator<Item=__T>>(iter: __I) -> Self {
+                iter.into_iter().fold(
+                    <Self as __bit_collection::BitCollection>::EMPTY,
+                    __bit_collection::BitCollection::inserting,
+                )
             }
         }
 
-        impl<T: Into<#name>> Extend<T> for #name {
+        impl<#params __T: Into<#ty>> Extend<__T> for #ty #where_clause {
             #[inline]
-            fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+            fn extend<__I: IntoIterator<Item=__T>>(&mut self, iter: __I) {
                 use #std::iter::FromIterator;
-                self.insert(Self::from_iter(iter));
+                __bit_collection::BitCollection::insert(self, Self::from_iter(iter));
             }
         }
 
-        impl IntoIterator for #name {
+        impl #impl_generics IntoIterator for #ty #where_clause {
             type IntoIter = #iter<Self>;
             type Item = #item;
 
@@ -257,11 +1030,86 @@ fn impl_bit_collection(ast: &syn::DeriveInput) -> quote::Tokens {
             }
         }
 
-        impl BitCollection for #name {
+        /// Conversions to and from bytes of the bits.
+        #[allow(dead_code)]
+        impl #impl_generics #ty #where_clause {
+            /// Returns the bits as bytes in little-endian order.
+            #[inline]
+            pub const fn to_le_bytes(&self) -> [u8; ::#std::mem::size_of::<#backing>()] {
+                self.#bits.to_le_bytes()
+            }
+
+            /// Returns the bits as bytes in big-endian order.
+            #[inline]
+            pub const fn to_be_bytes(&self) -> [u8; ::#std::mem::size_of::<#backing>()] {
+                self.#bits.to_be_bytes()
+            }
+
+            /// Creates a collection from bytes in little-endian order, or
+            /// returns `None` if a bit does not correspond to an item.
+            #[inline]
+            pub const fn from_le_bytes(
+                bytes: [u8; ::#std::mem::size_of::<#backing>()]
+            ) -> Option<Self> {
+                let x = #backing::from_le_bytes(bytes);
+                if x & !#mask == 0 { Some(#from_x) } else { None }
+            }
+
+            /// Creates a collection from bytes in big-endian order, or returns
+            /// `None` if a bit does not correspond to an item.
+            #[inline]
+            pub const fn from_be_bytes(
+                bytes: [u8; ::#std::mem::size_of::<#backing>()]
+            ) -> Option<Self> {
+                let x = #backing::from_be_bytes(bytes);
+                if x & !#mask == 0 { Some(#from_x) } else { None }
+            }
+
+            /// Creates a collection from bytes in little-endian order,
+            /// removing any bit that does not correspond to an item.
+            #[inline]
+            pub const fn from_le_bytes_truncate(
+                bytes: [u8; ::#std::mem::size_of::<#backing>()]
+            ) -> Self {
+                let x = #backing::from_le_bytes(bytes);
+                #from_x_masked
+            }
+
+            /// Creates a collection from bytes in big-endian order, removing
+            /// any bit that does not correspond to an item.
+            #[inline]
+            pub const fn from_be_bytes_truncate(
+                bytes: [u8; ::#std::mem::size_of::<#backing>()]
+            ) -> Self {
+                let x = #backing::from_be_bytes(bytes);
+                #from_x_masked
+            }
+        }
+
+        impl #impl_generics __bit_collection::BitCollection for #ty #where_clause {
             const FULL: Self = #full;
 
             const EMPTY: Self = #empty;
 
+            type Bits = #backing;
+
+            #[inline]
+            fn bits(&self) -> #backing {
+                self.#bits
+            }
+
+            #[inline]
+            unsafe fn bits_mut(&mut self) -> &mut #backing {
+                &mut self.#bits
+            }
+
+            #[inline]
+            unsafe fn from_bits_unchecked(x: #backing) -> Self {
+                #from_x
+            }
+
+            #with_bits
+
             #[inline]
             fn len(&self) -> usize {
                 self.#bits.count_ones() as _
@@ -286,42 +1134,77 @@ fn impl_bit_collection(ast: &syn::DeriveInput) -> quote::Tokens {
             #[inline]
             unsafe fn msb_unchecked(&self) -> #item {
                 use #std::mem::size_of;
-                let val = size_of::<#name>() * 8 - 1;
+                let val = size_of::<#backing>() * 8 - 1;
                 let raw = val ^ self.#bits.leading_zeros() as usize;
                 #item_from_raw
             }
 
             #[inline]
-            fn remove_lsb(&mut self) {
-                self.#bits &= self.#bits.wrapping_sub(1);
+            fn contains<__T: Into<Self>>(&self, other: __T) -> bool {
+                let other = other.into().#bits;
+                self.#bits & other == other
             }
+        }
 
-            #[inline]
-            fn remove_msb(&mut self) {
-                self.pop_msb();
-            }
+        #bitflags_compat
+        #enumset
+        #fixedbitset
+        #(#pod)*
+        #(#project)*
+        #product_impl
+        #bytemuck
+        #default_impl
+        #clone_impl
+        #copy_impl
+        #partial_eq_impl
+        #eq_impl
+        #hash_impl
+        #partial_ord_impl
+        #ord_impl
+    }; }
+}
 
-            #[inline]
-            fn pop_lsb(&mut self) -> Option<Self::Item> {
-                self.lsb().map(|x| {
-                    self.remove_lsb();
-                    x
-                })
+/// Returns the constant value of a field that doesn't hold the bits.
+///
+/// Default values must be usable in `FULL` and `EMPTY`, so only types whose
+/// `Default` is known to be constant are supported without `#[bit_default]`.
+/// Returns `None` for any other type.
+fn default_value(field: &syn::Field, std: &Ident) -> Option<Ident> {
+    for attr in &field.attrs {
+        if let MetaItem::NameValue(ref ident, Lit::Str(ref s, _)) = attr.value {
+            if ident == "bit_default" {
+                return Some(s.as_str().into());
             }
+        }
+    }
+    let value = match type_name(&field.ty) {
+        Some("PhantomData") => return Some(format!("::{}::marker::PhantomData", std).into()),
+        Some("u8") | Some("u16") | Some("u32") | Some("u64") | Some("u128") |
+        Some("usize") | Some("i8") | Some("i16") | Some("i32") | Some("i64") |
+        Some("i128") | Some("isize") => "0",
+        Some("f32") | Some("f64") => "0.0",
+        Some("bool") => "false",
+        Some("char") => "'\\0'",
+        _ => return None,
+    };
+    Some(value.into())
+}
 
-            #[inline]
-            fn pop_msb(&mut self) -> Option<#item> {
-                self.msb().map(|x| {
-                    self.#bits ^= #name::from(x).#bits;
-                    x
-                })
-            }
+/// Returns the last segment of a path type, ignoring generic arguments.
+fn type_name(ty: &Ty) -> Option<&str> {
+    match *ty {
+        Ty::Path(_, ref p) => p.segments.last().map(|s| s.ident.as_ref()),
+        _ => None,
+    }
+}
 
-            #[inline]
-            fn contains<T: Into<Self>>(&self, other: T) -> bool {
-                let other = other.into().#bits;
-                self.#bits & other == other
-            }
+/// Extracts the path of a type that may be surrounded by parentheses.
+fn extract_path(mut ty: &Ty) -> &syn::Path {
+    loop {
+        match *ty {
+            Ty::Paren(ref b) => ty = b,
+            Ty::Path(_, ref p) => return p,
+            _ => panic!("Incompatible type: {:?}", ty),
         }
     }
 }
@@ -0,0 +1,200 @@
+use core::fmt;
+
+use BitCollection;
+
+/// Displays a [`BitCollection`](trait.BitCollection.html) as a grid of `.`
+/// and `X` characters, with rank and file labels.
+///
+/// Cells correspond to the items of [`FULL`] in iteration order, filling each
+/// rank from left to right. The first rank is at the bottom, like a chess
+/// board.
+///
+/// # Examples
+///
+/// ```
+/// # include!("../templates/imports.rs");
+/// # include!("../templates/castle_rights.rs");
+/// # fn main() {
+/// let rights = CastleRights::from(CastleRight::WhiteKingside)
+///     | CastleRight::BlackQueenside;
+///
+/// let board = BoardDisplay::new(&rights).unwrap();
+/// assert_eq!(board.to_string(), "2 . X\n1 X .\n  a b\n");
+///
+/// let parsed: CastleRights = parse_board(&board.to_string()).unwrap();
+/// assert_eq!(parsed.len(), 2);
+/// # }
+/// ```
+///
+/// [`FULL`]: trait.BitCollection.html#associatedconstant.FULL
+pub struct BoardDisplay<'a, C: 'a> {
+    collection: &'a C,
+    width: usize,
+}
+
+impl<'a, C: BitCollection> BoardDisplay<'a, C> {
+    /// Creates a square grid display for `collection`.
+    ///
+    /// Returns `None` if the number of items in [`FULL`] is not a perfect
+    /// square.
+    ///
+    /// [`FULL`]: trait.BitCollection.html#associatedconstant.FULL
+    pub fn new(collection: &'a C) -> Option<Self> {
+        let len = C::FULL.len();
+        let width = (1..len + 1).find(|w| w * w >= len).unwrap_or(0);
+        if width * width == len {
+            Self::with_width(collection, width)
+        } else {
+            None
+        }
+    }
+
+    /// Creates a grid display for `collection` with `width` files per rank.
+    ///
+    /// Returns `None` if `width` is not between 1 and 26, or if it does not
+    /// evenly divide the number of items in [`FULL`].
+    ///
+    /// [`FULL`]: trait.BitCollection.html#associatedconstant.FULL
+    pub fn with_width(collection: &'a C, width: usize) -> Option<Self> {
+        let len = C::FULL.len();
+        if width == 0 || width > MAX_WIDTH || len == 0 || len % width != 0 {
+            None
+        } else {
+            Some(BoardDisplay { collection, width })
+        }
+    }
+
+    /// Returns the number of files per rank.
+    #[inline]
+    pub fn width(&self) -> usize { self.width }
+
+    /// Returns the number of ranks.
+    #[inline]
+    pub fn height(&self) -> usize { C::FULL.len() / self.width }
+}
+
+impl<'a, C: BitCollection> fmt::Display for BoardDisplay<'a, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let height = self.height();
+        let label_width = digits(height);
+
+        for rank in (0..height).rev() {
+            write!(f, "{:>1$}", rank + 1, label_width)?;
+            let row = C::FULL.into_iter().skip(rank * self.width).take(self.width);
+            for item in row {
+                let cell = if self.collection.contains(item) { 'X' } else { '.' };
+                write!(f, " {}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        write!(f, "{:>1$}", "", label_width)?;
+        for file in 0..self.width {
+            write!(f, " {}", (b'a' + file as u8) as char)?;
+        }
+        writeln!(f)
+    }
+}
+
+impl<'a, C: BitCollection> fmt::Debug for BoardDisplay<'a, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// An error returned when parsing a grid with
+/// [`parse_board`](fn.parse_board.html).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ParseBoardError {
+    /// A cell was neither `.` nor `X`.
+    InvalidCell(char),
+    /// A rank has a different number of files than the rank above it.
+    UnevenRanks,
+    /// The number of cells does not match the number of items in `FULL`.
+    CellCount(usize),
+}
+
+impl fmt::Display for ParseBoardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseBoardError::InvalidCell(c) => {
+                write!(f, "invalid board cell {:?}", c)
+            },
+            ParseBoardError::UnevenRanks => {
+                f.write_str("board ranks have different widths")
+            },
+            ParseBoardError::CellCount(n) => {
+                write!(f, "board has {} cells, which does not match the collection", n)
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ParseBoardError {}
+
+/// Parses a grid of `.` and `X` characters into a collection.
+///
+/// This reads the output of [`BoardDisplay`](struct.BoardDisplay.html). Rank
+/// and file labels are optional and ignored, and `x` is accepted in place of
+/// `X`. The top line is the last rank.
+pub fn parse_board<C: BitCollection>(s: &str) -> Result<C, ParseBoardError> {
+    // Each rank is shifted up as the next one is read, leaving the top rank in
+    // the highest bits. No collection has more than 128 items.
+    let mut cells = 0u128;
+    let mut count = 0;
+    let mut width = None;
+
+    for line in s.lines() {
+        let mut tokens = line.split_whitespace().peekable();
+        match tokens.peek() {
+            Some(t) if t.bytes().all(|b| b.is_ascii_digit()) => { tokens.next(); },
+            Some(&"a") => continue,
+            Some(_) => {},
+            None => continue,
+        }
+
+        let mut row = 0u128;
+        let mut files = 0;
+        for token in tokens {
+            for c in token.chars() {
+                let set = match c {
+                    '.' => false,
+                    'X' | 'x' => true,
+                    _ => return Err(ParseBoardError::InvalidCell(c)),
+                };
+                if set && files < 128 {
+                    row |= 1 << files;
+                }
+                files += 1;
+            }
+        }
+
+        if *width.get_or_insert(files) != files {
+            return Err(ParseBoardError::UnevenRanks);
+        }
+        cells = (if files < 128 { cells << files } else { 0 }) | row;
+        count += files;
+    }
+
+    if count != C::FULL.len() {
+        return Err(ParseBoardError::CellCount(count));
+    }
+
+    Ok(C::FULL.into_iter()
+        .enumerate()
+        .filter(|&(i, _)| cells >> i & 1 != 0)
+        .map(|(_, item)| item)
+        .collect())
+}
+
+const MAX_WIDTH: usize = 26;
+
+fn digits(mut n: usize) -> usize {
+    let mut digits = 1;
+    while n >= 10 {
+        n /= 10;
+        digits += 1;
+    }
+    digits
+}
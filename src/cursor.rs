@@ -0,0 +1,117 @@
+use {BitCollection, Word};
+
+/// A cursor over the items of a [`BitCollection`](trait.BitCollection.html)
+/// that can move both ways without consuming the collection.
+///
+/// The cursor is positioned at an item, which may not be in the collection,
+/// or at a "ghost" position between the last item and the first. Moving past
+/// either end goes to the ghost, and moving again wraps around.
+///
+/// This is created by [`cursor`](trait.BitCollection.html#method.cursor).
+#[derive(Debug)]
+pub struct Cursor<'a, C: BitCollection + 'a> {
+    collection: &'a C,
+    // The bit of the current position, or zero at the ghost.
+    bit: C::Bits,
+}
+
+impl<'a, C: BitCollection> Clone for Cursor<'a, C> {
+    #[inline]
+    fn clone(&self) -> Self { *self }
+}
+
+impl<'a, C: BitCollection> Copy for Cursor<'a, C> {}
+
+impl<'a, C: BitCollection> Cursor<'a, C> {
+    /// Creates a cursor at the ghost position of `collection`.
+    #[inline]
+    pub(crate) fn new(collection: &'a C) -> Self {
+        Cursor { collection, bit: Word::ZERO }
+    }
+
+    /// Returns the collection that `self` moves over.
+    #[inline]
+    pub fn collection(&self) -> &'a C {
+        self.collection
+    }
+
+    /// Returns the item at the current position if it is in the collection.
+    #[inline]
+    pub fn current(&self) -> Option<C::Item> {
+        if self.collection.bits() & self.bit == Word::ZERO { None } else {
+            unsafe { Some(C::from_bits_unchecked(self.bit).lsb_unchecked()) }
+        }
+    }
+
+    /// Returns whether `self` is at the ghost position.
+    #[inline]
+    pub fn is_ghost(&self) -> bool {
+        self.bit == Word::ZERO
+    }
+
+    /// Moves to `item`, whether or not it is in the collection.
+    #[inline]
+    pub fn seek(&mut self, item: C::Item) {
+        self.bit = C::from(item).bits();
+    }
+
+    /// Moves to the ghost position.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.bit = Word::ZERO;
+    }
+
+    /// Moves to the next item in the collection, or to the ghost position if
+    /// there is none.
+    #[inline]
+    pub fn move_next(&mut self) {
+        self.bit = self.next_bit();
+    }
+
+    /// Moves to the previous item in the collection, or to the ghost position
+    /// if there is none.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        self.bit = self.prev_bit();
+    }
+
+    /// Returns the next item in the collection without moving.
+    #[inline]
+    pub fn peek_next(&self) -> Option<C::Item> {
+        self.item(self.next_bit())
+    }
+
+    /// Returns the previous item in the collection without moving.
+    #[inline]
+    pub fn peek_prev(&self) -> Option<C::Item> {
+        self.item(self.prev_bit())
+    }
+
+    #[inline]
+    fn next_bit(&self) -> C::Bits {
+        let bits = self.collection.bits();
+        if self.bit == Word::ZERO {
+            bits.isolate_lowest()
+        } else {
+            let above = !(self.bit | self.bit.wrapping_sub(Word::ONE));
+            (bits & above).isolate_lowest()
+        }
+    }
+
+    #[inline]
+    fn prev_bit(&self) -> C::Bits {
+        let bits = self.collection.bits();
+        if self.bit == Word::ZERO {
+            bits.isolate_highest()
+        } else {
+            (bits & self.bit.wrapping_sub(Word::ONE)).isolate_highest()
+        }
+    }
+
+    #[inline]
+    fn item(&self, bit: C::Bits) -> Option<C::Item> {
+        if bit == Word::ZERO { None } else {
+            unsafe { Some(C::from_bits_unchecked(bit).lsb_unchecked()) }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use core::iter::FusedIterator;
+
+use {BitCollection, Word};
+
+/// A draining iterator over the items of a
+/// [`BitCollection`](trait.BitCollection.html).
+///
+/// This is created by [`drain`] and [`drain_range`]. Items are removed from the
+/// collection as they are iterated over, and any items left in the range are
+/// removed when the iterator is dropped.
+///
+/// [`drain`]: trait.BitCollection.html#method.drain
+/// [`drain_range`]: trait.BitCollection.html#method.drain_range
+pub struct Drain<'a, C: BitCollection + 'a> {
+    collection: &'a mut C,
+    mask: C::Bits,
+}
+
+impl<'a, C: BitCollection> Drain<'a, C> {
+    /// Creates an iterator that drains the items of `collection` whose bits
+    /// are in `mask`.
+    #[inline]
+    pub(crate) fn new(collection: &'a mut C, mask: C::Bits) -> Self {
+        Drain { collection, mask }
+    }
+
+    /// Returns the bits that are left to be drained.
+    #[inline]
+    fn remaining(&self) -> C::Bits {
+        self.collection.bits() & self.mask
+    }
+
+    /// Removes `bit` from the collection and returns its item.
+    #[inline]
+    fn take(&mut self, bit: C::Bits) -> Option<C::Item> {
+        if bit == Word::ZERO {
+            return None;
+        }
+        // `bit` is the lowest or highest bit left to drain, which is a single
+        // bit of the collection and so that of one of its items. Clearing it
+        // leaves the collection with a subset of its bits.
+        unsafe {
+            let item = C::from_bits_unchecked(bit).lsb_unchecked();
+            *self.collection.bits_mut() ^= bit;
+            Some(item)
+        }
+    }
+}
+
+impl<'a, C: BitCollection> Iterator for Drain<'a, C> {
+    type Item = C::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let bit = self.remaining().isolate_lowest();
+        self.take(bit)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, C: BitCollection> DoubleEndedIterator for Drain<'a, C> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let bit = self.remaining().isolate_highest();
+        self.take(bit)
+    }
+}
+
+impl<'a, C: BitCollection> ExactSizeIterator for Drain<'a, C> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.remaining().count_ones() as usize
+    }
+}
+
+impl<'a, C: BitCollection> FusedIterator for Drain<'a, C> {}
+
+impl<'a, C: BitCollection> Drop for Drain<'a, C> {
+    #[inline]
+    fn drop(&mut self) {
+        // Clearing the bits of the range leaves a subset of the collection.
+        unsafe { *self.collection.bits_mut() &= !self.mask };
+    }
+}
@@ -0,0 +1,297 @@
+//! Compact byte encodings of a [`BitCollection`](../trait.BitCollection.html).
+//!
+//! Two formats are provided:
+//!
+//! - [Varint](fn.encode_varint.html): the bits as an unsigned [LEB128]
+//!   integer, seven bits per byte. This suits dense collections and those with
+//!   only low items.
+//!
+//! - [Indices](fn.encode_indices.html): the bit index of each item as a
+//!   varint, each relative to the one before it. This suits sparse
+//!   collections with high items, such as a few items of a 128-bit set.
+//!
+//! [`encode`](fn.encode.html) picks whichever format is shorter and prefixes
+//! it with a tag byte, which [`decode`](fn.decode.html) reads back.
+//!
+//! Decoding fails if a bit does not correspond to an item of
+//! [`FULL`](../trait.BitCollection.html#associatedconstant.FULL).
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(not(feature = "std"))]
+//! # extern crate core;
+//! # #[macro_use]
+//! # extern crate bit_collection;
+//! # use bit_collection::*;
+//! use bit_collection::encoding;
+//!
+//! #[derive(Copy, Clone, Debug, PartialEq)]
+//! pub struct Id(u8);
+//!
+//! #[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+//! #[bit(Id, retr = "0")]
+//! pub struct Ids(u128);
+//!
+//! # fn main() {
+//! let ids = Ids::from(Id(100)) | Id(120);
+//!
+//! let mut buf = [0; encoding::MAX_ENCODED_LEN];
+//! let len = encoding::encode(&ids, &mut buf).unwrap();
+//! assert_eq!(len, 3);
+//!
+//! let decoded: Ids = encoding::decode(&buf[..len]).unwrap();
+//! assert_eq!(decoded, ids);
+//! # }
+//! ```
+//!
+//! [LEB128]: https://en.wikipedia.org/wiki/LEB128
+
+use core::fmt;
+
+use {BitCollection, Word};
+
+/// The largest number of bytes written by [`encode`](fn.encode.html).
+///
+/// This is a tag byte and the varint encoding of 128 bits.
+pub const MAX_ENCODED_LEN: usize = 1 + 128usize.div_ceil(PAYLOAD as usize);
+
+/// The tag byte of the varint format.
+const VARINT: u8 = 0;
+
+/// The tag byte of the indices format.
+const INDICES: u8 = 1;
+
+/// The payload bits of a varint byte.
+const PAYLOAD: u32 = 7;
+
+/// The bit of a varint byte that is set if more bytes follow.
+const CONTINUE: u8 = 0x80;
+
+/// An error returned when a buffer is too small to encode into.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct EncodeError {
+    /// The number of bytes needed.
+    pub needed: usize,
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "buffer is too small to encode into, {} bytes needed", self.needed)
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for EncodeError {}
+
+/// An error returned when decoding a collection.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum DecodeError {
+    /// The input ended in the middle of a varint, or had no tag byte.
+    Truncated,
+    /// The input continued after the varint.
+    TrailingBytes,
+    /// The tag byte is not one written by [`encode`](fn.encode.html).
+    UnknownFormat(u8),
+    /// A bit index, or a varint byte, is beyond the bits of the collection.
+    Overflow,
+    /// A bit does not correspond to an item of `FULL`.
+    InvalidItem(u32),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecodeError::Truncated => f.write_str("encoded collection is truncated"),
+            DecodeError::TrailingBytes => {
+                f.write_str("encoded collection is followed by trailing bytes")
+            },
+            DecodeError::UnknownFormat(tag) => {
+                write!(f, "unknown collection encoding format {}", tag)
+            },
+            DecodeError::Overflow => {
+                f.write_str("encoded collection has more bits than the collection")
+            },
+            DecodeError::InvalidItem(index) => {
+                write!(f, "bit {} does not correspond to an item", index)
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for DecodeError {}
+
+/// Returns the number of bytes written by
+/// [`encode_varint`](fn.encode_varint.html).
+#[inline]
+pub fn varint_len<C: BitCollection>(collection: &C) -> usize {
+    let bits = collection.bits();
+    let used = <C::Bits as Word>::BITS - bits.leading_zeros();
+    used.div_ceil(PAYLOAD).max(1) as usize
+}
+
+/// Returns the number of bytes written by
+/// [`encode_indices`](fn.encode_indices.html).
+#[inline]
+pub fn indices_len<C: BitCollection>(collection: &C) -> usize {
+    // Bit indices are below 128, and so each delta fits in a single byte.
+    collection.len()
+}
+
+/// Returns the number of bytes written by [`encode`](fn.encode.html).
+#[inline]
+pub fn encoded_len<C: BitCollection>(collection: &C) -> usize {
+    1 + varint_len(collection).min(indices_len(collection))
+}
+
+/// Writes the bits of `collection` to `buf` as a varint, returning the number
+/// of bytes written.
+pub fn encode_varint<C: BitCollection>(
+    collection: &C,
+    buf: &mut [u8],
+) -> Result<usize, EncodeError> {
+    let len = varint_len(collection);
+    if buf.len() < len {
+        return Err(EncodeError { needed: len });
+    }
+
+    let mut bits = collection.bits();
+    for (i, byte) in buf[..len].iter_mut().enumerate() {
+        *byte = low_byte(bits);
+        bits = bits >> PAYLOAD;
+        if i + 1 < len {
+            *byte |= CONTINUE;
+        }
+    }
+    Ok(len)
+}
+
+/// Reads a collection written by [`encode_varint`](fn.encode_varint.html).
+///
+/// All of `bytes` must be used by the varint.
+pub fn decode_varint<C: BitCollection>(bytes: &[u8]) -> Result<C, DecodeError> {
+    let (bits, len) = read_varint::<C::Bits>(bytes)?;
+    if len != bytes.len() {
+        return Err(DecodeError::TrailingBytes);
+    }
+    validate(bits)
+}
+
+/// Writes the bit index of each item of `collection` to `buf`, returning the
+/// number of bytes written.
+///
+/// The first index is written as-is, and every other index as the number of
+/// bits skipped since the previous one.
+pub fn encode_indices<C: BitCollection>(
+    collection: &C,
+    buf: &mut [u8],
+) -> Result<usize, EncodeError> {
+    let len = indices_len(collection);
+    if buf.len() < len {
+        return Err(EncodeError { needed: len });
+    }
+
+    let mut bits = collection.bits();
+    let mut next = 0;
+    for byte in &mut buf[..len] {
+        let index = bits.trailing_zeros();
+        *byte = (index - next) as u8;
+        next = index + 1;
+        bits = bits.clear_lowest();
+    }
+    Ok(len)
+}
+
+/// Reads a collection written by [`encode_indices`](fn.encode_indices.html).
+pub fn decode_indices<C: BitCollection>(mut bytes: &[u8]) -> Result<C, DecodeError> {
+    let mut bits = <C::Bits as Word>::ZERO;
+    let mut next = 0u32;
+    while !bytes.is_empty() {
+        let (skip, len) = read_varint::<u32>(bytes)?;
+        bytes = &bytes[len..];
+
+        let index = next.checked_add(skip).ok_or(DecodeError::Overflow)?;
+        if index >= <C::Bits as Word>::BITS {
+            return Err(DecodeError::Overflow);
+        }
+        bits |= <C::Bits as Word>::ONE << index;
+        next = index + 1;
+    }
+    validate(bits)
+}
+
+/// Writes `collection` to `buf` in whichever format is shorter, returning the
+/// number of bytes written.
+///
+/// At most [`MAX_ENCODED_LEN`](constant.MAX_ENCODED_LEN.html) bytes are
+/// written.
+pub fn encode<C: BitCollection>(collection: &C, buf: &mut [u8]) -> Result<usize, EncodeError> {
+    let len = encoded_len(collection);
+    if buf.len() < len {
+        return Err(EncodeError { needed: len });
+    }
+
+    let (tag, rest) = buf.split_at_mut(1);
+    if varint_len(collection) <= indices_len(collection) {
+        tag[0] = VARINT;
+        encode_varint(collection, rest)
+    } else {
+        tag[0] = INDICES;
+        encode_indices(collection, rest)
+    }.map(|n| n + 1)
+}
+
+/// Reads a collection written by [`encode`](fn.encode.html).
+pub fn decode<C: BitCollection>(bytes: &[u8]) -> Result<C, DecodeError> {
+    match bytes.split_first() {
+        Some((&VARINT, rest)) => decode_varint(rest),
+        Some((&INDICES, rest)) => decode_indices(rest),
+        Some((&tag, _)) => Err(DecodeError::UnknownFormat(tag)),
+        None => Err(DecodeError::Truncated),
+    }
+}
+
+/// Returns the low payload bits of `bits` as a byte.
+#[inline]
+fn low_byte<W: Word>(bits: W) -> u8 {
+    (0..PAYLOAD).filter(|&i| bits & W::ONE << i != W::ZERO)
+                .fold(0, |byte, i| byte | 1 << i)
+}
+
+/// Reads a varint from the start of `bytes`, returning it and the number of
+/// bytes read.
+fn read_varint<W: Word>(bytes: &[u8]) -> Result<(W, usize), DecodeError> {
+    let mut value = W::ZERO;
+    for (i, &byte) in bytes.iter().enumerate() {
+        // Any byte past the width of `W` is an overflow, even if its payload
+        // is empty, so the index below can't overflow on long inputs.
+        if i >= (W::BITS as usize).div_ceil(PAYLOAD as usize) {
+            return Err(DecodeError::Overflow);
+        }
+        let start = i as u32 * PAYLOAD;
+        for bit in 0..PAYLOAD {
+            if byte & 1 << bit != 0 {
+                let index = start + bit;
+                if index >= W::BITS {
+                    return Err(DecodeError::Overflow);
+                }
+                value |= W::ONE << index;
+            }
+        }
+        if byte & CONTINUE == 0 {
+            return Ok((value, i + 1));
+        }
+    }
+    Err(DecodeError::Truncated)
+}
+
+/// Converts `bits` into a collection if they are all part of `FULL`.
+fn validate<C: BitCollection>(bits: C::Bits) -> Result<C, DecodeError> {
+    let invalid = bits & !C::FULL.bits();
+    if invalid != Word::ZERO {
+        return Err(DecodeError::InvalidItem(invalid.trailing_zeros()));
+    }
+    // `invalid` is empty, so every bit read from the input is one of `FULL`.
+    unsafe { Ok(C::from_bits_unchecked(bits)) }
+}
@@ -7,7 +7,7 @@
 //!
 //! ```toml
 //! [dependencies]
-//! bit_collection = "0.2.3"
+//! bit_collection = "0.3"
 //! ```
 //!
 //! and this to your crate root:
@@ -31,6 +31,13 @@
 //! #[bit(Type, ...)]
 //! ```
 //!
+//! Types that aren't a single identifier, such as generic types, can be given
+//! as a string.
+//!
+//! ```rust,ignore
+//! #[bit("Id<T>", ...)]
+//! ```
+//!
 //! ## Mask:
 //! A mask indicating the valid bits of the collection. This should be a
 //! constant expression.
@@ -59,15 +66,180 @@
 //! ```
 //!
 //! ## Iterator:
-//! The iterator for a given [`BitCollection`]. If [`BitIter`] isn't imported
-//! as-is, this option allows for specifying its module path.
+//! The iterator for a given [`BitCollection`]. If not provided, it is this
+//! crate's [`BitIter`], which doesn't need to be in scope.
+//!
+//! ```rust,ignore
+//! #[bit(..., iter = "MyIter", ...)]
+//! ```
+//!
+//! ## Crate:
+//! The path to this crate, for when it is renamed in `Cargo.toml` or used
+//! through a re-export. If not provided, the generated code links it as
+//! `bit_collection`. Macros can pass `$crate` without quotes, as
+//! [`bit_set!`](macro.bit_set.html) does.
+//!
+//! ```rust,ignore
+//! #[bit(..., crate = "::bc", ...)]
+//! ```
+//!
+//! ## Traits:
+//! Standard traits to implement, from `Default`, `Clone`, `Copy`,
+//! `PartialEq`, `Eq`, `Hash`, `PartialOrd` and `Ord`. `Copy`, `Eq` and `Ord`
+//! also implement `Clone`, `PartialEq` and `PartialOrd`.
+//!
+//! Unlike those from `#[derive]`, these don't require generic parameters to
+//! implement the trait, and only the bits are compared and hashed. `Default`
+//! returns [`EMPTY`][EMPTY]. `Ord` orders collections by their items as
+//! iterated over, rather than by the value of their bits.
+//!
+//! ```rust,ignore
+//! #[bit(..., traits(Default, Copy, Eq, Hash, Ord), ...)]
+//! ```
+//!
+//! ## `bitflags` Compatibility:
+//! Generates inherent methods with the same signatures as those generated by
+//! [`bitflags`]: `bits`, `from_bits`, `from_bits_truncate`, `all`, `empty`,
+//! `is_all`, `is_empty`, `contains`, `intersects`, `insert`, `remove`,
+//! `toggle` and `set`.
+//!
+//! ```rust,ignore
+//! #[bit(..., bitflags_compat, ...)]
+//! ```
+//!
+//! To migrate from `bitflags`, turn its flags into an item type and keep the
+//! named combinations as associated constants. Because inherent methods take
+//! priority over trait methods, `insert`, `remove`, `toggle` and `set` return
+//! `()` like they do in `bitflags`, rather than `&mut Self`.
+//!
+//! ## Interop:
+//! With the `enumset` or `fixedbitset` feature, these options implement
+//! `From<Self>` and `TryFrom` for [`EnumSet`] or [`FixedBitSet`]. Conversions
+//! into `Self` fail with the original set if it has items outside of
+//! [`FULL`][FULL].
+//!
+//! The generated code names the other crate by its absolute path, such as
+//! `::enumset::EnumSet`, so the deriving crate must depend on it directly
+//! under its own name. Before the 2018 edition, that crate must also be
+//! declared with `extern crate` at its root.
+//!
+//! A `FixedBitSet` has the same bits set as
+//! [`BitCollection::bits`](trait.BitCollection.html#tymethod.bits), and an
+//! `EnumSet` has the same items.
 //!
 //! ```rust,ignore
-//! extern crate bit_collection as bc;
+//! #[bit(..., enumset, fixedbitset, ...)]
+//! ```
+//!
+//! ## Product:
+//! Makes the item a pair of the items of two other collection types, `A` and
+//! `B`. Bit `i * width + j` is the pair of the items of bit `i` of `A` and
+//! bit `j` of `B`, so each item of `A` has a row of `B`'s bits. The width of
+//! a row is the number of bits of `B` up to its last item. This replaces the
+//! item type and `mask`, which are computed from the [`FULL`][FULL] of each
+//! type.
 //!
-//! #[bit(..., iter = "bc::BitIter", ...)]
+//! The type must not be generic, and must have enough bits for a row for each
+//! bit of `A` up to its last item. Both `A` and `B` must be derived.
+//! Inherent `row`, `column` and `from_rows` methods are also generated.
+//!
+//! ```rust,ignore
+//! #[bit(product(Ranks, Files), ...)]
 //! ```
 //!
+//! ## Projection:
+//! Implements [`Project`] for each listed collection type, using the function
+//! that maps an item of `Self` to an item of that type. Items mapped outside
+//! of the other type's [`FULL`][FULL] are left out. This does not support
+//! generic types.
+//!
+//! With the `std` feature, the bits that each item maps to are cached in a
+//! table the first time a projection runs, so that projecting is a lookup per
+//! item along with an atomic load to find the cache. The function isn't
+//! `const`, so the cache can't be built at compile time. Without `std`, each
+//! projection calls the function on every item.
+//!
+//! ```rust,ignore
+//! #[bit(..., project(Files = "square_file", Ranks = "square_rank"), ...)]
+//! ```
+//!
+//! ## Zero-Copy:
+//! The `bytemuck` option implements [`Zeroable`] and [`Pod`], so that
+//! collections can be cast to and from bytes. The `zerocopy` option is meant
+//! to accompany [`zerocopy`]'s own derives, which don't allow for manual
+//! impls.
+//!
+//! Both options check that any bytes make a valid collection: the type must be
+//! `#[repr(transparent)]` and not generic, other fields must be
+//! [`PhantomData`], and [`FULL`][FULL] must be `!0`. The deriving crate must
+//! also depend on the other crate.
+//!
+//! ```rust,ignore
+//! #[derive(BitCollection, FromBytes, IntoBytes, Immutable, KnownLayout)]
+//! #[bit(..., bytemuck, zerocopy, ...)]
+//! #[repr(transparent)]
+//! ```
+//!
+//! # Generics
+//!
+//! Collections may have generic parameters, which are forwarded to every
+//! generated impl along with the `where` clause. Marker fields can be given as
+//! [`PhantomData`] (see [fields](#fields)).
+//!
+//! ```
+//! # include!("../templates/imports.rs");
+//! use std::marker::PhantomData;
+//!
+//! #[derive(Copy, Clone)]
+//! pub struct Id<T>(u8, PhantomData<T>);
+//!
+//! /// A set of `Id`s of one kind.
+//! #[derive(BitCollection)]
+//! #[bit("Id<T>", retr = "0")]
+//! pub struct Ids<T>(u64, PhantomData<T>);
+//!
+//! # fn main() {}
+//! ```
+//!
+//! # Fields
+//!
+//! The first field holds the bits, unless another field is marked with
+//! `#[bit_field]`. Every other field is set to its default value when a
+//! collection is created, and is carried along by operators such as `|` and
+//! `-`, which keep the fields of their left-hand side. Methods that split a
+//! collection, such as `partition` and `runs`, clone the other fields into
+//! each part through [`with_bits`], so those fields must be `Clone`.
+//!
+//! Because [`FULL`][FULL] and [`EMPTY`][EMPTY] are constants, default values
+//! must be constant expressions. [`PhantomData`] and primitive numbers, `bool`
+//! and `char` take their `Default` value. Other fields must be given a value
+//! with `#[bit_default = "expr"]`.
+//!
+//! ```
+//! # include!("../templates/imports.rs");
+//! # #[derive(Copy, Clone)] pub struct Square(u8);
+//! #[derive(BitCollection)]
+//! #[bit(Square, retr = "0")]
+//! pub struct Board {
+//!     #[bit_default = "\"main\""]
+//!     name: &'static str,
+//!     moves: u32,
+//!     #[bit_field]
+//!     squares: u64,
+//! }
+//!
+//! # fn main() {}
+//! ```
+//!
+//! # Bytes
+//!
+//! Derived collections have `to_le_bytes` and `to_be_bytes` methods, which
+//! return the bytes of the bits as an array the size of the backing integer.
+//! They're converted back with `from_le_bytes` and `from_be_bytes`, which
+//! return `None` if a bit does not correspond to an item, or with
+//! `from_le_bytes_truncate` and `from_be_bytes_truncate`, which remove those
+//! bits.
+//!
 //! # Examples
 //!
 //! In computer chess, one popular way of representing the occupants of a board
@@ -80,8 +252,8 @@
 //! pub struct Square(u8);
 //!
 //! /// A set of sixty-four `Square`s.
-//! #[bit(Square, mask = "!0", retr = "0")]
 //! #[derive(BitCollection)]
+//! #[bit(Square, mask = "!0", retr = "0")]
 //! pub struct Bitboard(u64);
 //!
 //! # fn main() {}
@@ -100,8 +272,8 @@
 //! }
 //!
 //! /// A set of `CastleRight`s.
-//! #[bit(CastleRight, mask = "0b1111")]
 //! #[derive(BitCollection)]
+//! #[bit(CastleRight, mask = "0b1111")]
 //! pub struct CastleRights {
 //!     bits: u8
 //! }
@@ -137,6 +309,16 @@
 //! [`BitCollection`]: trait.BitCollection.html
 //! [`BitIter`]: struct.BitIter.html
 //! [FULL]: trait.BitCollection.html#associatedconstant.FULL
+//! [EMPTY]: trait.BitCollection.html#associatedconstant.EMPTY
+//! [`Project`]: trait.Project.html
+//! [`with_bits`]: trait.BitCollection.html#method.with_bits
+//! [`PhantomData`]: https://doc.rust-lang.org/std/marker/struct.PhantomData.html
+//! [`bitflags`]: https://docs.rs/bitflags
+//! [`EnumSet`]: https://docs.rs/enumset
+//! [`FixedBitSet`]: https://docs.rs/fixedbitset
+//! [`Zeroable`]: https://docs.rs/bytemuck/1/bytemuck/trait.Zeroable.html
+//! [`Pod`]: https://docs.rs/bytemuck/1/bytemuck/trait.Pod.html
+//! [`zerocopy`]: https://docs.rs/zerocopy
 //! [bitboard]: https://chessprogramming.wikispaces.com/Bitboards
 
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -147,7 +329,7 @@ extern crate core;
 use core::borrow::{Borrow, BorrowMut};
 use core::cmp::Ordering;
 use core::iter::FromIterator;
-use core::ops;
+use core::ops::{self, Bound, RangeBounds};
 
 // Reexport derive macro.
 #[allow(unused_imports)]
@@ -156,6 +338,63 @@ extern crate bit_collection_derive;
 #[doc(hidden)]
 pub use bit_collection_derive::*;
 
+#[macro_use]
+mod macros;
+
+mod board;
+pub use board::{BoardDisplay, ParseBoardError, parse_board};
+
+mod zobrist;
+pub use zobrist::ZobristTable;
+
+mod drain;
+pub use drain::Drain;
+
+mod runs;
+pub use runs::Runs;
+
+mod cursor;
+pub use cursor::Cursor;
+
+mod order;
+pub use order::{Order, OrderedIter};
+
+pub mod word;
+pub use word::Word;
+
+pub mod encoding;
+
+#[cfg(feature = "rayon")]
+extern crate rayon;
+
+#[cfg(feature = "rayon")]
+mod par;
+#[cfg(feature = "rayon")]
+pub use par::ParBitIter;
+
+#[cfg(feature = "proptest")]
+extern crate proptest;
+#[cfg(feature = "quickcheck")]
+extern crate quickcheck;
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "rand")]
+use rand::Rng;
+
+#[cfg(feature = "enumset")]
+extern crate enumset;
+#[cfg(feature = "enumset")]
+use enumset::{EnumSet, EnumSetType};
+
+#[cfg(feature = "fixedbitset")]
+extern crate fixedbitset;
+#[cfg(feature = "fixedbitset")]
+use fixedbitset::FixedBitSet;
+
 /// A type that represents a collection of bits that can be iterated over.
 pub trait BitCollection: From<<Self as IntoIterator>::Item>
     + From<BitIter<Self>>
@@ -178,6 +417,44 @@ pub trait BitCollection: From<<Self as IntoIterator>::Item>
     /// An empty instance with no bits set.
     const EMPTY: Self;
 
+    /// The integer type that holds the bits of `Self`.
+    type Bits: Word;
+
+    /// Returns the bits of `self`.
+    fn bits(&self) -> Self::Bits;
+
+    /// Returns a mutable reference to the bits of `self`.
+    ///
+    /// # Safety
+    ///
+    /// Bits not set in [`FULL`](#associatedconstant.FULL) must not be set
+    /// through the returned reference.
+    unsafe fn bits_mut(&mut self) -> &mut Self::Bits;
+
+    /// Creates an instance from `bits` without masking them.
+    ///
+    /// # Safety
+    ///
+    /// `bits` must not have bits set that are not set in
+    /// [`FULL`](#associatedconstant.FULL).
+    unsafe fn from_bits_unchecked(bits: Self::Bits) -> Self;
+
+    /// Creates an instance from `bits` without masking them, keeping any other
+    /// fields of `self`.
+    ///
+    /// Methods that split `self` into new collections use this, so that each
+    /// part keeps the fields of `self` rather than their default values. The
+    /// derive implements it by cloning the other fields.
+    ///
+    /// # Safety
+    ///
+    /// `bits` must not have bits set that are not set in
+    /// [`FULL`](#associatedconstant.FULL).
+    #[inline]
+    unsafe fn with_bits(&self, bits: Self::Bits) -> Self {
+        Self::from_bits_unchecked(bits)
+    }
+
     /// Returns the number of bits set in `self`.
     ///
     /// If checking whether `self` has zero, one, or multiple bits set, use
@@ -209,6 +486,89 @@ pub trait BitCollection: From<<Self as IntoIterator>::Item>
         }
     }
 
+    /// Returns the quantity of bits set, along with the item if there's only
+    /// one.
+    ///
+    /// This is the same as [`quantity`](#method.quantity), but doesn't require
+    /// getting the item separately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # include!("../templates/imports.rs");
+    /// # include!("../templates/castle_rights.rs");
+    /// # fn main() {
+    /// let rights = CastleRights::from(CastleRight::BlackKingside);
+    ///
+    /// match rights.quantity_item() {
+    ///     QuantityItem::Single(right) => { /* ... */ },
+    ///     QuantityItem::Multiple => unreachable!(),
+    ///     QuantityItem::None => unreachable!(),
+    /// }
+    /// # }
+    /// ```
+    #[inline]
+    fn quantity_item(&self) -> QuantityItem<Self::Item> {
+        use self::QuantityItem::*;
+        if self.is_empty() {
+            None
+        } else if self.has_multiple() {
+            Multiple
+        } else {
+            Single(unsafe { self.lsb_unchecked() })
+        }
+    }
+
+    /// Compares the number of bits set with `n`.
+    ///
+    /// This clears at most `n` bits rather than counting all of them, which is
+    /// faster than comparing [`len`](#tymethod.len) with a small `n` on wide
+    /// collections.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # include!("../templates/imports.rs");
+    /// # include!("../templates/castle_rights.rs");
+    /// # fn main() {
+    /// use std::cmp::Ordering;
+    ///
+    /// let rights = CastleRights::from(CastleRight::WhiteKingside)
+    ///     | CastleRight::BlackKingside;
+    ///
+    /// assert_eq!(rights.len_cmp(2), Ordering::Equal);
+    /// assert!(rights.has_at_least(1) && !rights.has_at_least(3));
+    /// assert!(rights.has_at_most(2) && !rights.has_at_most(1));
+    /// # }
+    /// ```
+    #[inline]
+    fn len_cmp(&self, n: usize) -> Ordering {
+        let mut bits = self.bits();
+        for _ in 0..n {
+            if bits == Word::ZERO {
+                return Ordering::Less;
+            }
+            bits = bits.clear_lowest();
+        }
+        if bits == Word::ZERO { Ordering::Equal } else { Ordering::Greater }
+    }
+
+    /// Returns whether `self` has at least `n` bits set.
+    ///
+    /// See [`len_cmp`](#method.len_cmp).
+    #[inline]
+    fn has_at_least(&self, n: usize) -> bool {
+        self.len_cmp(n) != Ordering::Less
+    }
+
+    /// Returns whether `self` has at most `n` bits set.
+    ///
+    /// See [`len_cmp`](#method.len_cmp).
+    #[inline]
+    fn has_at_most(&self, n: usize) -> bool {
+        self.len_cmp(n) != Ordering::Greater
+    }
+
     /// Returns `self` as an iterator over itself.
     ///
     /// # Examples
@@ -229,7 +589,70 @@ pub trait BitCollection: From<<Self as IntoIterator>::Item>
     /// ```
     #[inline]
     fn as_iter(&mut self) -> &mut BitIter<Self> {
-        unsafe { &mut *(self as *mut _ as *mut _) }
+        // `BitIter` is a transparent wrapper around `Self`.
+        unsafe { &mut *(self as *mut Self as *mut BitIter<Self>) }
+    }
+
+    /// Returns an iterator that removes the items of `self` as it goes.
+    ///
+    /// Any items not iterated over are removed when the iterator is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # include!("../templates/imports.rs");
+    /// # include!("../templates/castle_rights.rs");
+    /// # fn main() {
+    /// let mut rights = CastleRights::FULL;
+    ///
+    /// assert_eq!(rights.drain().take(2).count(), 2);
+    /// assert!(rights.is_empty());
+    /// # }
+    /// ```
+    #[inline]
+    fn drain(&mut self) -> Drain<'_, Self> {
+        Drain::new(self, !<Self::Bits as Word>::ZERO)
+    }
+
+    /// Returns an iterator that removes the items of `self` within `range` as
+    /// it goes, where items are ordered as they are iterated over.
+    ///
+    /// Any items in `range` not iterated over are removed when the iterator is
+    /// dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # include!("../templates/imports.rs");
+    /// # include!("../templates/castle_rights.rs");
+    /// # fn main() {
+    /// use CastleRight::*;
+    ///
+    /// let mut rights = CastleRights::FULL;
+    /// let drained: Vec<_> = rights.drain_range(BlackKingside..BlackQueenside).collect();
+    ///
+    /// assert_eq!(drained.len(), 2);
+    /// assert_eq!(rights.bits(), (CastleRights::from(WhiteKingside) | BlackQueenside).bits());
+    /// # }
+    /// ```
+    fn drain_range<R>(&mut self, range: R) -> Drain<'_, Self>
+        where R: RangeBounds<Self::Item>, Self::Item: Clone
+    {
+        let bit = |item: &Self::Item| Self::from(item.clone()).bits();
+        let below = |bit: Self::Bits| bit.wrapping_sub(Word::ONE);
+
+        let all = !<Self::Bits as Word>::ZERO;
+        let start = match range.start_bound() {
+            Bound::Included(item) => !below(bit(item)),
+            Bound::Excluded(item) => !(bit(item) | below(bit(item))),
+            Bound::Unbounded => all,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(item) => bit(item) | below(bit(item)),
+            Bound::Excluded(item) => below(bit(item)),
+            Bound::Unbounded => all,
+        };
+        Drain::new(self, start & end)
     }
 
     /// Converts `self` into the only bit set.
@@ -255,28 +678,697 @@ pub trait BitCollection: From<<Self as IntoIterator>::Item>
         }
     }
 
+    /// Returns the least significant item in `self` that is more significant
+    /// than `item`.
+    #[inline]
+    fn next_after(&self, item: Self::Item) -> Option<Self::Item> {
+        let bit = Self::from(item).bits();
+        let above = self.bits() & !(bit | bit.wrapping_sub(Word::ONE));
+        if above == Word::ZERO { None } else {
+            unsafe { Some(Self::from_bits_unchecked(above).lsb_unchecked()) }
+        }
+    }
+
+    /// Returns the most significant item in `self` that is less significant
+    /// than `item`.
+    #[inline]
+    fn prev_before(&self, item: Self::Item) -> Option<Self::Item> {
+        let bit = Self::from(item).bits();
+        let below = self.bits() & bit.wrapping_sub(Word::ONE);
+        if below == Word::ZERO { None } else {
+            unsafe { Some(Self::from_bits_unchecked(below).msb_unchecked()) }
+        }
+    }
+
+    /// Returns the item in `self` after `item`, wrapping around to the least
+    /// significant item.
+    ///
+    /// This is `item` itself if it is the only item in `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # include!("../templates/imports.rs");
+    /// # include!("../templates/castle_rights.rs");
+    /// # fn main() {
+    /// use CastleRight::*;
+    ///
+    /// let ready = CastleRights::from(WhiteKingside) | WhiteQueenside;
+    ///
+    /// assert_eq!(ready.next_cyclic(WhiteKingside), Some(WhiteQueenside));
+    /// assert_eq!(ready.next_cyclic(WhiteQueenside), Some(WhiteKingside));
+    /// assert_eq!(ready.next_cyclic(BlackQueenside), Some(WhiteKingside));
+    /// # }
+    /// ```
+    #[inline]
+    fn next_cyclic(&self, item: Self::Item) -> Option<Self::Item> {
+        let bit = Self::from(item).bits();
+        let above = self.bits() & !(bit | bit.wrapping_sub(Word::ONE));
+        let bits = if above == Word::ZERO { self.bits() } else { above };
+        if bits == Word::ZERO { None } else {
+            unsafe { Some(Self::from_bits_unchecked(bits).lsb_unchecked()) }
+        }
+    }
+
+    /// Returns the item in `self` before `item`, wrapping around to the most
+    /// significant item.
+    ///
+    /// This is `item` itself if it is the only item in `self`.
+    #[inline]
+    fn prev_cyclic(&self, item: Self::Item) -> Option<Self::Item> {
+        let bit = Self::from(item).bits();
+        let below = self.bits() & bit.wrapping_sub(Word::ONE);
+        let bits = if below == Word::ZERO { self.bits() } else { below };
+        if bits == Word::ZERO { None } else {
+            unsafe { Some(Self::from_bits_unchecked(bits).msb_unchecked()) }
+        }
+    }
+
+    /// Returns an iterator over the items of `self` from highest to lowest
+    /// priority in `order`.
+    ///
+    /// Building the iterator takes time proportional to the number of items,
+    /// and each step after that is constant time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # include!("../templates/imports.rs");
+    /// # include!("../templates/castle_rights.rs");
+    /// # fn main() {
+    /// use CastleRight::*;
+    ///
+    /// const QUEENSIDE_FIRST: Order<8> = Order::new([2, 3, 0, 1, 4, 5, 6, 7]);
+    ///
+    /// let rights: Vec<_> = CastleRights::FULL.iter_ordered(&QUEENSIDE_FIRST).collect();
+    /// assert_eq!(rights, [WhiteQueenside, BlackQueenside, WhiteKingside, BlackKingside]);
+    /// # }
+    /// ```
+    ///
+    /// The order must have as many bits as [`Bits`](#associatedtype.Bits),
+    /// which is checked at compile time.
+    ///
+    /// ```compile_fail
+    /// # include!("../templates/imports.rs");
+    /// # include!("../templates/castle_rights.rs");
+    /// # fn main() {
+    /// let order = Order::new([0, 1, 2, 3]);
+    /// CastleRights::FULL.iter_ordered(&order);
+    /// # }
+    /// ```
+    #[inline]
+    fn iter_ordered<'a, const N: usize>(&self, order: &'a Order<N>)
+        -> OrderedIter<'a, Self>
+    {
+        OrderedIter::new(self, order)
+    }
+
+    /// Returns a cursor over the items of `self`, starting at the ghost
+    /// position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # include!("../templates/imports.rs");
+    /// # include!("../templates/castle_rights.rs");
+    /// # fn main() {
+    /// use CastleRight::*;
+    ///
+    /// let rights = CastleRights::from(BlackKingside) | BlackQueenside;
+    /// let mut cursor = rights.cursor();
+    ///
+    /// cursor.seek(WhiteQueenside);
+    /// assert_eq!(cursor.current(), None);
+    /// assert_eq!(cursor.peek_prev(), Some(BlackKingside));
+    ///
+    /// cursor.move_next();
+    /// assert_eq!(cursor.current(), Some(BlackQueenside));
+    ///
+    /// cursor.move_next();
+    /// assert!(cursor.is_ghost());
+    /// # }
+    /// ```
+    #[inline]
+    fn cursor(&self) -> Cursor<'_, Self> {
+        Cursor::new(self)
+    }
+
+    /// Returns the least and most significant bits in `self` if `self` is not
+    /// empty.
+    #[inline]
+    fn bounds(&self) -> Option<(Self::Item, Self::Item)> {
+        if self.is_empty() { None } else {
+            unsafe { Some((self.lsb_unchecked(), self.msb_unchecked())) }
+        }
+    }
+
+    /// Returns an iterator over the maximal runs of consecutive bits in
+    /// `self`, from least to most significant.
+    ///
+    /// Each run is a collection of its items. Use [`bounds`](#method.bounds)
+    /// to get its first and last items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # include!("../templates/imports.rs");
+    /// # include!("../templates/castle_rights.rs");
+    /// # fn main() {
+    /// use CastleRight::*;
+    ///
+    /// let rights = CastleRights::FULL - BlackKingside;
+    /// let bounds: Vec<_> = rights.runs().map(|run| run.bounds().unwrap()).collect();
+    ///
+    /// assert_eq!(bounds, [
+    ///     (WhiteKingside, WhiteKingside),
+    ///     (WhiteQueenside, BlackQueenside),
+    /// ]);
+    /// # }
+    /// ```
+    #[inline]
+    fn runs(&self) -> Runs<Self> {
+        Runs::new(self)
+    }
+
+    /// Returns the longest run of consecutive bits in `self`, or the least
+    /// significant of those that are tied.
+    ///
+    /// This is empty if `self` is empty.
+    fn longest_run(&self) -> Self {
+        // No bits are set outside of `FULL` when none are set at all.
+        let empty = unsafe { self.with_bits(Word::ZERO) };
+        self.runs().fold(empty, |longest, run| {
+            if run.len() > longest.len() { run } else { longest }
+        })
+    }
+
+    /// Returns the `n` least significant consecutive bits in `self`, if any.
+    ///
+    /// This is the first place that `n` items can be allocated as a block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # include!("../templates/imports.rs");
+    /// # include!("../templates/castle_rights.rs");
+    /// # fn main() {
+    /// use CastleRight::*;
+    ///
+    /// let free = CastleRights::FULL - BlackKingside;
+    /// let block = free.first_run_of_len(2).unwrap();
+    ///
+    /// assert_eq!(block.bounds(), Some((WhiteQueenside, BlackQueenside)));
+    /// assert!(free.first_run_of_len(3).is_none());
+    /// # }
+    /// ```
+    fn first_run_of_len(&self, n: usize) -> Option<Self> {
+        let bits = self.bits();
+        let max = <Self::Bits as Word>::BITS;
+        if n > max as usize {
+            return None;
+        }
+        let n = n as u32;
+
+        // Keep only the bits that start `k` consecutive bits, doubling `k`
+        // until it reaches `n`
+        let mut starts = bits;
+        let mut k = 1;
+        while k < n {
+            let shift = k.min(n - k);
+            starts &= starts >> shift;
+            k += shift;
+        }
+
+        if n == 0 {
+            // No bits are set outside of `FULL` when none are set at all.
+            unsafe { Some(self.with_bits(Word::ZERO)) }
+        } else if starts == Word::ZERO {
+            None
+        } else {
+            // `starts` only keeps bits whose next `n - 1` bits are also set in
+            // `bits`, so the `n` bits from its lowest are all bits of `self`.
+            let run = runs::low_bits::<Self::Bits>(n) << starts.trailing_zeros();
+            unsafe { Some(self.with_bits(run)) }
+        }
+    }
+
     /// Returns the least significant bit in `self` without checking whether
     /// `self` is empty.
+    ///
+    /// # Safety
+    ///
+    /// `self` must not be empty.
     unsafe fn lsb_unchecked(&self) -> Self::Item;
 
     /// Returns the most significant bit in `self` without checking whether
     /// `self` is empty.
+    ///
+    /// # Safety
+    ///
+    /// `self` must not be empty.
     unsafe fn msb_unchecked(&self) -> Self::Item;
 
+    /// Returns the `n`th least significant bit in `self`, or `None` if `self`
+    /// does not have more than `n` bits set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # include!("../templates/imports.rs");
+    /// # include!("../templates/castle_rights.rs");
+    /// # fn main() {
+    /// let rights = CastleRights::from(CastleRight::BlackKingside)
+    ///     | CastleRight::BlackQueenside;
+    ///
+    /// assert_eq!(rights.select(1), Some(CastleRight::BlackQueenside));
+    /// assert_eq!(rights.select(2), None);
+    /// # }
+    /// ```
+    #[inline]
+    fn select(&self, n: usize) -> Option<Self::Item> {
+        if n < self.len() {
+            unsafe { Some(self.select_unchecked(n)) }
+        } else {
+            None
+        }
+    }
+
+    /// Returns the `n`th least significant bit in `self` without checking
+    /// whether `self` has more than `n` bits set.
+    ///
+    /// # Safety
+    ///
+    /// `n` must be less than [`len`](#tymethod.len).
+    #[inline]
+    unsafe fn select_unchecked(&self, n: usize) -> Self::Item {
+        let bit = self.bits().select(n as u32);
+        Self::from_bits_unchecked(bit).lsb_unchecked()
+    }
+
+    /// Extracts the bits of `self` at the positions of the bits of `mask`,
+    /// packing them into the low bits of the result.
+    ///
+    /// This compresses a collection onto a dense index, such as for indexing
+    /// into a table of occupancies. It is the inverse of
+    /// [`deposit`](#method.deposit).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # include!("../templates/imports.rs");
+    /// # include!("../templates/castle_rights.rs");
+    /// # fn main() {
+    /// use CastleRight::*;
+    ///
+    /// let queenside = CastleRights::from(WhiteQueenside) | BlackQueenside;
+    /// let rights = CastleRights::from(BlackKingside) | BlackQueenside;
+    ///
+    /// let index = rights.extract(queenside);
+    /// assert_eq!(index, 0b10);
+    /// assert_eq!(CastleRights::deposit(index, queenside).len(), 1);
+    /// # }
+    /// ```
+    #[inline]
+    fn extract<T: Into<Self>>(&self, mask: T) -> Self::Bits {
+        self.bits().pext(mask.into().bits())
+    }
+
+    /// Deposits the low bits of `bits` into the positions of the bits of
+    /// `mask`, from least to most significant.
+    ///
+    /// This expands a dense index back into a collection. It is the inverse
+    /// of [`extract`](#method.extract).
+    #[inline]
+    fn deposit<T: Into<Self>>(bits: Self::Bits, mask: T) -> Self {
+        // `pdep` only sets bits that are set in `mask`, and the result keeps
+        // its other fields.
+        let mask = mask.into();
+        unsafe { mask.with_bits(bits.pdep(mask.bits())) }
+    }
+
+    /// Returns a uniformly random item in `self`, or `None` if `self` is
+    /// empty.
+    ///
+    /// This method is available with the `rand` feature.
+    #[cfg(feature = "rand")]
+    #[inline]
+    fn choose<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<Self::Item> {
+        let len = self.len();
+        if len == 0 { None } else {
+            unsafe { Some(self.select_unchecked(rng.gen_range(0..len))) }
+        }
+    }
+
+    /// Returns `k` distinct items of `self` chosen uniformly at random, or all
+    /// of `self` if it does not have more than `k` items.
+    ///
+    /// This method is available with the `rand` feature.
+    #[cfg(feature = "rand")]
+    fn choose_multiple<R: Rng + ?Sized>(&self, rng: &mut R, k: usize) -> Self {
+        let len = self.len();
+        let bits = self.bits();
+
+        // Choosing the items to leave out is faster when there are fewer.
+        let invert = k > len / 2;
+        let count = if invert { len.saturating_sub(k) } else { k };
+
+        let mut remaining = bits;
+        let mut chosen = <Self::Bits as Word>::ZERO;
+        for i in 0..count {
+            let bit = remaining.select(rng.gen_range(0..len - i) as u32);
+            remaining ^= bit;
+            chosen |= bit;
+        }
+        if invert {
+            chosen ^= bits;
+        }
+
+        // Every chosen bit was selected from the bits of `self`.
+        unsafe { self.with_bits(chosen) }
+    }
+
+    /// Returns a random subset of [`FULL`](#associatedconstant.FULL) where
+    /// each item is included with probability `p`.
+    ///
+    /// This method is available with the `rand` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not between 0 and 1.
+    #[cfg(feature = "rand")]
+    fn random_subset<R: Rng + ?Sized>(rng: &mut R, p: f64) -> Self {
+        let mut remaining = Self::FULL.bits();
+        let mut chosen = <Self::Bits as Word>::ZERO;
+        while remaining != Word::ZERO {
+            let bit = remaining.isolate_lowest();
+            if rng.gen_bool(p) {
+                chosen |= bit;
+            }
+            remaining ^= bit;
+        }
+
+        // The chosen bits are a subset of `FULL`.
+        unsafe { Self::from_bits_unchecked(chosen) }
+    }
+
+    /// Returns a [`FixedBitSet`] with the same bits set as
+    /// [`bits`](#tymethod.bits).
+    ///
+    /// This method is available with the `fixedbitset` feature.
+    ///
+    /// [`FixedBitSet`]: https://docs.rs/fixedbitset
+    #[cfg(feature = "fixedbitset")]
+    fn to_fixed_bit_set(&self) -> FixedBitSet {
+        let mut set = FixedBitSet::with_capacity(<Self::Bits as Word>::BITS as usize);
+        let mut bits = self.bits();
+        while bits != Word::ZERO {
+            set.insert(bits.trailing_zeros() as usize);
+            bits = bits.clear_lowest();
+        }
+        set
+    }
+
+    /// Converts the bits of a [`FixedBitSet`] into a collection, or returns
+    /// `None` if a bit does not correspond to an item of
+    /// [`FULL`](#associatedconstant.FULL).
+    ///
+    /// This method is available with the `fixedbitset` feature.
+    ///
+    /// [`FixedBitSet`]: https://docs.rs/fixedbitset
+    #[cfg(feature = "fixedbitset")]
+    fn from_fixed_bit_set(set: &FixedBitSet) -> Option<Self> {
+        let full = Self::FULL.bits();
+        let mut bits = <Self::Bits as Word>::ZERO;
+        for index in set.ones() {
+            if index >= <Self::Bits as Word>::BITS as usize {
+                return None;
+            }
+            let bit = <Self::Bits as Word>::ONE << index as u32;
+            if full & bit == Word::ZERO {
+                return None;
+            }
+            bits |= bit;
+        }
+
+        // The bits are a subset of `FULL`.
+        unsafe { Some(Self::from_bits_unchecked(bits)) }
+    }
+
+    /// Converts `self` into an [`EnumSet`] of the same items.
+    ///
+    /// This method is available with the `enumset` feature.
+    ///
+    /// [`EnumSet`]: https://docs.rs/enumset
+    #[cfg(feature = "enumset")]
+    #[inline]
+    fn into_enum_set(self) -> EnumSet<Self::Item> where Self::Item: EnumSetType {
+        self.into_iter().collect()
+    }
+
+    /// Converts an [`EnumSet`] into a collection of the same items, or returns
+    /// `None` if an item is not in [`FULL`](#associatedconstant.FULL).
+    ///
+    /// Each item of the set is taken to be the bit at its index in the set,
+    /// which is the case for items whose discriminants are their bits.
+    ///
+    /// This method is available with the `enumset` feature.
+    ///
+    /// [`EnumSet`]: https://docs.rs/enumset
+    #[cfg(feature = "enumset")]
+    fn from_enum_set(set: EnumSet<Self::Item>) -> Option<Self>
+        where Self::Item: EnumSetType
+    {
+        // Items past the width of `Self::Bits` can't be shifted into place,
+        // so check each index before making a bit out of it.
+        let mut indices = set.try_as_u128()?;
+        let full = Self::FULL.bits();
+        let mut bits = <Self::Bits as Word>::ZERO;
+        while indices != 0 {
+            let index = indices.trailing_zeros();
+            if index >= <Self::Bits as Word>::BITS {
+                return None;
+            }
+            let bit = <Self::Bits as Word>::ONE << index;
+            if full & bit == Word::ZERO {
+                return None;
+            }
+            bits |= bit;
+            indices &= indices - 1;
+        }
+
+        // The bits are a subset of `FULL`.
+        unsafe { Some(Self::from_bits_unchecked(bits)) }
+    }
+
     /// Removes the least significant bit from `self`.
-    fn remove_lsb(&mut self);
+    #[inline]
+    fn remove_lsb(&mut self) {
+        unsafe {
+            let bits = self.bits_mut();
+            *bits = bits.clear_lowest();
+        }
+    }
 
     /// Removes the most significant bit from `self`.
-    fn remove_msb(&mut self);
+    #[inline]
+    fn remove_msb(&mut self) {
+        unsafe {
+            let bits = self.bits_mut();
+            *bits ^= bits.isolate_highest();
+        }
+    }
 
     /// Removes the least significant bit from `self` and returns it.
-    fn pop_lsb(&mut self) -> Option<Self::Item>;
+    ///
+    /// The bit is removed without branching, even if `self` is empty.
+    #[inline]
+    fn pop_lsb(&mut self) -> Option<Self::Item> {
+        let item = self.lsb();
+        self.remove_lsb();
+        item
+    }
 
     /// Removes the most significant bit from `self` and returns it.
-    fn pop_msb(&mut self) -> Option<Self::Item>;
+    ///
+    /// The bit is removed without branching, even if `self` is empty.
+    #[inline]
+    fn pop_msb(&mut self) -> Option<Self::Item> {
+        let item = self.msb();
+        self.remove_msb();
+        item
+    }
 
     /// Returns whether `self` contains the value.
-    fn contains<T: Into<Self>>(&self, T) -> bool;
+    fn contains<T: Into<Self>>(&self, other: T) -> bool;
+
+    /// Returns the number of items in `self` that come before `item` in
+    /// iteration order.
+    ///
+    /// The rank of an item within [`FULL`](#associatedconstant.FULL) is its
+    /// index among all possible items.
+    #[inline]
+    fn rank(&self, item: Self::Item) -> usize {
+        let below = Self::from(item).bits().wrapping_sub(Word::ONE);
+        (self.bits() & below).count_ones() as usize
+    }
+
+    /// Retains only the items for which `f` returns `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # include!("../templates/imports.rs");
+    /// # include!("../templates/castle_rights.rs");
+    /// # fn main() {
+    /// use CastleRight::*;
+    ///
+    /// let mut rights = CastleRights::FULL;
+    /// rights.retain(|right| match right {
+    ///     WhiteKingside | WhiteQueenside => true,
+    ///     _ => false,
+    /// });
+    /// assert_eq!(rights.len(), 2);
+    /// assert!(rights.contains(WhiteQueenside));
+    /// # }
+    /// ```
+    fn retain<F: FnMut(Self::Item) -> bool>(&mut self, mut f: F) {
+        // `rest` has the bits of `self`, which is valid, and is only used to
+        // walk over its items.
+        let mut rest = unsafe { self.with_bits(self.bits()) };
+        let mut kept = <Self::Bits as Word>::ZERO;
+        while !rest.is_empty() {
+            let bit = rest.bits().isolate_lowest();
+            // `rest` was just checked to not be empty.
+            if f(unsafe { rest.lsb_unchecked() }) {
+                kept |= bit;
+            }
+            rest.remove_lsb();
+        }
+        // Each kept bit was taken from `rest`, whose bits are those of `self`.
+        unsafe { *self.bits_mut() = kept };
+    }
+
+    /// Returns only the items of `self` for which `f` returns `true`.
+    ///
+    /// See [`retain`](#method.retain).
+    #[inline]
+    fn filtered<F: FnMut(Self::Item) -> bool>(mut self, f: F) -> Self {
+        self.retain(f);
+        self
+    }
+
+    /// Splits `self` into the items for which `f` returns `true` and those for
+    /// which it returns `false`.
+    #[inline]
+    fn partition<F: FnMut(Self::Item) -> bool>(mut self, f: F) -> (Self, Self) {
+        let bits = self.bits();
+        self.retain(f);
+        let rest = bits ^ self.bits();
+        // `retain` only clears bits, so `rest` is the bits it cleared out of
+        // `bits`, which were those of a valid `self`.
+        let rest = unsafe { self.with_bits(rest) };
+        (self, rest)
+    }
+
+    /// Splits `self` into its first `n` items and the rest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # include!("../templates/imports.rs");
+    /// # include!("../templates/castle_rights.rs");
+    /// # fn main() {
+    /// let (first, rest) = CastleRights::FULL.take_n(3);
+    /// assert_eq!(first.len(), 3);
+    /// assert_eq!(rest.into_bit(), CastleRights::FULL.msb());
+    /// # }
+    /// ```
+    #[inline]
+    fn take_n(mut self, n: usize) -> (Self, Self) {
+        let bits = self.bits();
+        let bit = if n < <Self::Bits as Word>::BITS as usize {
+            bits.select(n as u32)
+        } else {
+            Word::ZERO
+        };
+        let rest = if bit == Word::ZERO { bit } else {
+            bits & !bit.wrapping_sub(Word::ONE)
+        };
+        // `rest` is `bits` from the `n`th bit up, and `bits ^ rest` is the
+        // bits below it, so both are subsets of the bits of `self`.
+        unsafe {
+            let rest = self.with_bits(rest);
+            *self.bits_mut() = bits ^ rest.bits();
+            (self, rest)
+        }
+    }
+
+    /// Splits `self` into its first items for which `f` returns `true` and the
+    /// rest, starting with the first item for which it returns `false`.
+    fn take_while<F: FnMut(Self::Item) -> bool>(mut self, mut f: F) -> (Self, Self) {
+        // `rest` starts with the bits of `self`, which is valid.
+        let mut rest = unsafe { self.with_bits(self.bits()) };
+        // `rest` is checked to not be empty before taking its lowest item.
+        while !rest.is_empty() && f(unsafe { rest.lsb_unchecked() }) {
+            rest.remove_lsb();
+        }
+        // `rest` only lost bits, so it is a subset of `self` and clearing it
+        // leaves the bits that were removed from it.
+        unsafe {
+            *self.bits_mut() ^= rest.bits();
+        }
+        (self, rest)
+    }
+
+    /// Returns the collection of items that the items of `self` map to.
+    ///
+    /// For projecting a collection type often, see [`Project`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "std"))]
+    /// # extern crate core;
+    /// # #[macro_use]
+    /// # extern crate bit_collection;
+    /// # use bit_collection::*;
+    /// #[derive(Copy, Clone, Debug, PartialEq)]
+    /// pub struct Square(u8);
+    ///
+    /// #[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+    /// #[bit(Square, retr = "0")]
+    /// pub struct Squares(u64);
+    ///
+    /// #[derive(Copy, Clone, Debug, PartialEq)]
+    /// pub struct File(u8);
+    ///
+    /// #[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+    /// #[bit(File, retr = "0")]
+    /// pub struct Files(u8);
+    ///
+    /// # fn main() {
+    /// let squares = Squares::from(Square(2)) | Square(10) | Square(63);
+    /// let files: Files = squares.map_into(|s| File(s.0 % 8));
+    ///
+    /// assert_eq!(files, Files::from(File(2)) | File(7));
+    /// # }
+    /// ```
+    ///
+    /// [`Project`]: trait.Project.html
+    #[inline]
+    fn map_into<D, F>(self, f: F) -> D
+        where D: BitCollection, F: FnMut(Self::Item) -> D::Item
+    {
+        self.into_iter().map(f).collect()
+    }
+
+    /// Returns the union of the collections that the items of `self` map to.
+    #[inline]
+    fn flat_map_into<D, F>(self, mut f: F) -> D
+        where D: BitCollection, F: FnMut(Self::Item) -> D
+    {
+        self.into_iter().fold(D::EMPTY, |acc, item| acc | f(item))
+    }
 
     /// Returns the result of removing the value from `self`.
     #[inline]
@@ -354,6 +1446,7 @@ pub trait BitCollection: From<<Self as IntoIterator>::Item>
 
 /// An iterator over the bits of a [`BitCollection`](trait.BitCollection.html).
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+#[repr(transparent)]
 pub struct BitIter<C: BitCollection>(pub C);
 
 impl<C: BitCollection> From<C> for BitIter<C> {
@@ -463,3 +1556,104 @@ impl Ord for Quantity {
         }
     }
 }
+
+/// How many bits are set in a [`BitCollection`](trait.BitCollection.html),
+/// along with the item if there's only one, as returned by
+/// [`quantity_item`](trait.BitCollection.html#method.quantity_item).
+///
+/// This is ordered like [`Quantity`](enum.Quantity.html), with `Single` items
+/// ordered by their own value.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum QuantityItem<T> {
+    // NOTE: The variant order matches that of Quantity
+    /// Multiple bits set.
+    Multiple,
+    /// Single bit set.
+    Single(T),
+    /// No bits set.
+    None,
+}
+
+impl<T> QuantityItem<T> {
+    /// Returns the quantity without the item.
+    #[inline]
+    pub fn quantity(&self) -> Quantity {
+        match *self {
+            QuantityItem::Multiple => Quantity::Multiple,
+            QuantityItem::Single(_) => Quantity::Single,
+            QuantityItem::None => Quantity::None,
+        }
+    }
+
+    /// Returns the item if there's only one.
+    #[inline]
+    pub fn single(self) -> Option<T> {
+        match self {
+            QuantityItem::Single(item) => Some(item),
+            _ => None,
+        }
+    }
+}
+
+impl<T> From<QuantityItem<T>> for Quantity {
+    #[inline]
+    fn from(quantity: QuantityItem<T>) -> Quantity {
+        quantity.quantity()
+    }
+}
+
+impl<T: Ord> PartialOrd for QuantityItem<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> Ord for QuantityItem<T> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (QuantityItem::Single(a), QuantityItem::Single(b)) => a.cmp(b),
+            _ => self.quantity().cmp(&other.quantity()),
+        }
+    }
+}
+
+/// An extension of `Iterator` for getting the [`Quantity`](enum.Quantity.html)
+/// of items.
+pub trait IterQuantity: Iterator + Sized {
+    /// Returns the quantity of items in `self`, taking at most two of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bit_collection::{IterQuantity, Quantity};
+    ///
+    /// assert_eq!((0..).quantity(), Quantity::Multiple);
+    /// assert_eq!(Some(1).into_iter().quantity(), Quantity::Single);
+    /// assert_eq!((0..0).quantity(), Quantity::None);
+    /// ```
+    #[inline]
+    fn quantity(mut self) -> Quantity {
+        if self.next().is_none() {
+            Quantity::None
+        } else if self.next().is_none() {
+            Quantity::Single
+        } else {
+            Quantity::Multiple
+        }
+    }
+}
+
+impl<I: Iterator> IterQuantity for I {}
+
+/// A projection of a [`BitCollection`](trait.BitCollection.html) onto the
+/// collection `D`.
+///
+/// This is implemented by `#[bit(project(D = "f"))]`. The result is that of
+/// [`map_into(f)`](trait.BitCollection.html#method.map_into), limited to the
+/// items of [`D::FULL`](trait.BitCollection.html#associatedconstant.FULL).
+pub trait Project<D: BitCollection>: BitCollection {
+    /// Returns the collection of items that the items of `self` map to.
+    fn project(self) -> D;
+}
@@ -0,0 +1,77 @@
+/// Defines an item `enum` along with a collection of its variants.
+///
+/// The enum is given one variant per item, and the collection is a tuple
+/// struct backed by the given integer type, with a mask covering every
+/// variant. Both derive `Copy`, `Clone`, `Debug`, `PartialEq`, `Eq` and `Hash`,
+/// and the enum is `#[repr(u8)]`.
+///
+/// Each item also has an associated constant of the same name on the
+/// collection, holding only that item.
+///
+/// # Examples
+///
+/// ```
+/// # include!("../templates/imports.rs");
+/// bit_set! {
+///     /// A file permission.
+///     pub enum Perm { Read, Write, Exec }
+///
+///     /// A set of file permissions.
+///     pub struct Perms: u8;
+/// }
+///
+/// # fn main() {
+/// let perms = Perms::Read | Perm::Exec;
+///
+/// assert_eq!(Perms::FULL.len(), 3);
+/// assert_eq!(perms.into_iter().collect::<Vec<_>>(), [Perm::Read, Perm::Exec]);
+/// # }
+/// ```
+///
+/// A collection can hold as many items as its backing integer has bits.
+///
+/// ```compile_fail
+/// # include!("../templates/imports.rs");
+/// bit_set! {
+///     enum Large { A, B, C, D, E, F, G, H, I }
+///     struct Larges: u8;
+/// }
+/// # fn main() { let _ = Larges::FULL; }
+/// ```
+#[macro_export]
+macro_rules! bit_set {
+    (
+        $(#[$enum_attr:meta])*
+        $enum_vis:vis enum $item:ident {
+            $($(#[$variant_attr:meta])* $variant:ident),+ $(,)*
+        }
+
+        $(#[$struct_attr:meta])*
+        $struct_vis:vis struct $name:ident: $bits:ident;
+    ) => {
+        $(#[$enum_attr])*
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        #[repr(u8)]
+        $enum_vis enum $item {
+            $($(#[$variant_attr])* $variant),+
+        }
+
+        $(#[$struct_attr])*
+        #[derive($crate::BitCollection, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        #[bit($item, mask = "Self::__MASK", crate = $crate)]
+        $struct_vis struct $name($bits);
+
+        #[allow(non_upper_case_globals)]
+        impl $name {
+            #[doc(hidden)]
+            const __MASK: $bits = !0 >> (
+                <$bits as $crate::Word>::BITS - [$(stringify!($variant)),+].len() as u32
+            );
+
+            $(
+                #[allow(missing_docs)]
+                pub const $variant: $name = $name(1 << $item::$variant as u32);
+            )+
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+use {BitCollection, Word};
+
+/// A permutation of the `N` bits of a collection, from highest to lowest
+/// priority.
+///
+/// This is used by [`iter_ordered`] to iterate over items in a custom order.
+///
+/// # Examples
+///
+/// ```
+/// use bit_collection::Order;
+///
+/// // Center files of a chess board first
+/// const FILES: Order<8> = Order::new([3, 4, 2, 5, 1, 6, 0, 7]);
+///
+/// assert_eq!(FILES.rank(3), 0);
+/// assert_eq!(FILES.rank(7), 7);
+/// ```
+///
+/// [`iter_ordered`]: trait.BitCollection.html#method.iter_ordered
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Order<const N: usize> {
+    by_rank: [u8; N],
+    rank: [u8; N],
+}
+
+impl<const N: usize> Order<N> {
+    /// Creates an order where the bit index at `by_rank[i]` has priority `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `by_rank` is not a permutation of `0..N`, or if `N` is
+    /// greater than 128.
+    pub const fn new(by_rank: [u8; N]) -> Self {
+        assert!(N <= 128, "An order may have at most 128 bits.");
+
+        let mut rank = [0u8; N];
+        let mut seen = 0u128;
+        let mut i = 0;
+        while i < N {
+            let index = by_rank[i] as usize;
+            assert!(index < N, "An order must only have bit indices below `N`.");
+            assert!(seen & 1 << index == 0, "An order must not repeat bit indices.");
+            seen |= 1 << index;
+            rank[index] = i as u8;
+            i += 1;
+        }
+        Order { by_rank, rank }
+    }
+
+    /// Returns the priority of the bit at `index`, where `0` is the highest.
+    #[inline]
+    pub const fn rank(&self, index: usize) -> usize {
+        self.rank[index] as usize
+    }
+
+    /// Returns the bit indices from highest to lowest priority.
+    #[inline]
+    pub const fn by_rank(&self) -> &[u8; N] {
+        &self.by_rank
+    }
+}
+
+/// An iterator over the items of a [`BitCollection`](trait.BitCollection.html)
+/// in the priority of an [`Order`](struct.Order.html).
+///
+/// This is created by
+/// [`iter_ordered`](trait.BitCollection.html#method.iter_ordered).
+pub struct OrderedIter<'a, C: BitCollection> {
+    // Bit `i` is set if the item with priority `i` is left.
+    ranks: C::Bits,
+    by_rank: &'a [u8],
+}
+
+/// Checks that an order of `N` bits fits the collection `C`.
+struct SameBits<C, const N: usize>(PhantomData<C>);
+
+impl<C: BitCollection, const N: usize> SameBits<C, N> {
+    const CHECK: () = assert!(
+        N == <C::Bits as Word>::BITS as usize,
+        "An order must have as many bits as the collection.",
+    );
+}
+
+impl<'a, C: BitCollection> OrderedIter<'a, C> {
+    #[inline]
+    pub(crate) fn new<const N: usize>(collection: &C, order: &'a Order<N>) -> Self {
+        // Fails to build rather than panicking if the sizes differ.
+        #[allow(clippy::let_unit_value)]
+        let () = SameBits::<C, N>::CHECK;
+
+        let mut bits = collection.bits();
+        let mut ranks = <C::Bits as Word>::ZERO;
+        while bits != Word::ZERO {
+            ranks |= <C::Bits as Word>::ONE << order.rank[bits.trailing_zeros() as usize] as u32;
+            bits = bits.clear_lowest();
+        }
+        OrderedIter { ranks, by_rank: &order.by_rank }
+    }
+
+    #[inline]
+    fn item(&self, rank: u32) -> C::Item {
+        let bit = <C::Bits as Word>::ONE << self.by_rank[rank as usize] as u32;
+        // The bit is that of an item in the collection.
+        unsafe { C::from_bits_unchecked(bit).lsb_unchecked() }
+    }
+}
+
+impl<'a, C: BitCollection> Clone for OrderedIter<'a, C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        OrderedIter { ranks: self.ranks, by_rank: self.by_rank }
+    }
+}
+
+impl<'a, C: BitCollection> fmt::Debug for OrderedIter<'a, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("OrderedIter")
+         .field("ranks", &self.ranks)
+         .finish()
+    }
+}
+
+impl<'a, C: BitCollection> Iterator for OrderedIter<'a, C> {
+    type Item = C::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<C::Item> {
+        if self.ranks == Word::ZERO {
+            return None;
+        }
+        let rank = self.ranks.trailing_zeros();
+        self.ranks = self.ranks.clear_lowest();
+        Some(self.item(rank))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, C: BitCollection> DoubleEndedIterator for OrderedIter<'a, C> {
+    #[inline]
+    fn next_back(&mut self) -> Option<C::Item> {
+        if self.ranks == Word::ZERO {
+            return None;
+        }
+        let rank = <C::Bits as Word>::BITS - 1 - self.ranks.leading_zeros();
+        self.ranks ^= <C::Bits as Word>::ONE << rank;
+        Some(self.item(rank))
+    }
+}
+
+impl<'a, C: BitCollection> ExactSizeIterator for OrderedIter<'a, C> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.ranks.count_ones() as usize
+    }
+}
+
+impl<'a, C: BitCollection> FusedIterator for OrderedIter<'a, C> {}
@@ -0,0 +1,114 @@
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+use rayon::iter::plumbing::{
+    bridge,
+    Consumer,
+    Producer,
+    ProducerCallback,
+    UnindexedConsumer,
+};
+
+use {BitCollection, BitIter, Word};
+
+/// A parallel iterator over the bits of a
+/// [`BitCollection`](trait.BitCollection.html).
+///
+/// This is created by calling `into_par_iter` on a
+/// [`BitIter`](struct.BitIter.html). Work is split by halving the remaining
+/// bits, so each bit is yielded exactly once across all threads.
+///
+/// # Examples
+///
+/// ```
+/// # include!("../templates/imports.rs");
+/// # include!("../templates/castle_rights.rs");
+/// extern crate rayon;
+/// use rayon::prelude::*;
+///
+/// # fn main() {
+/// let rights: Vec<_> = CastleRights::FULL.into_iter().into_par_iter().collect();
+/// assert_eq!(rights, CastleRights::FULL.into_iter().collect::<Vec<_>>());
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct ParBitIter<C>(C);
+
+impl<C> IntoParallelIterator for BitIter<C>
+    where C: BitCollection + Send, C::Item: Send
+{
+    type Iter = ParBitIter<C>;
+    type Item = C::Item;
+
+    #[inline]
+    fn into_par_iter(self) -> ParBitIter<C> {
+        ParBitIter(self.0)
+    }
+}
+
+impl<C> ParallelIterator for ParBitIter<C>
+    where C: BitCollection + Send, C::Item: Send
+{
+    type Item = C::Item;
+
+    #[inline]
+    fn drive_unindexed<U>(self, consumer: U) -> U::Result
+        where U: UnindexedConsumer<Self::Item>
+    {
+        bridge(self, consumer)
+    }
+
+    #[inline]
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+impl<C> IndexedParallelIterator for ParBitIter<C>
+    where C: BitCollection + Send, C::Item: Send
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    fn drive<U: Consumer<Self::Item>>(self, consumer: U) -> U::Result {
+        bridge(self, consumer)
+    }
+
+    #[inline]
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+        where CB: ProducerCallback<Self::Item>
+    {
+        callback.callback(BitProducer(self.0))
+    }
+}
+
+struct BitProducer<C>(C);
+
+impl<C> Producer for BitProducer<C>
+    where C: BitCollection + Send, C::Item: Send
+{
+    type Item = C::Item;
+    type IntoIter = BitIter<C>;
+
+    #[inline]
+    fn into_iter(self) -> BitIter<C> {
+        self.0.into_iter()
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let bits = self.0.bits();
+        let split = bits.select(index as u32);
+        let low = if split == Word::ZERO {
+            bits
+        } else {
+            bits & split.wrapping_sub(Word::ONE)
+        };
+
+        // Both halves are subsets of a valid collection.
+        unsafe {
+            (BitProducer(C::from_bits_unchecked(low)),
+             BitProducer(C::from_bits_unchecked(bits ^ low)))
+        }
+    }
+}
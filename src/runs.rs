@@ -0,0 +1,88 @@
+use core::iter::FusedIterator;
+
+use {BitCollection, Word};
+
+/// An iterator over the maximal runs of consecutive bits in a
+/// [`BitCollection`](trait.BitCollection.html), each as a collection.
+///
+/// This is created by [`runs`](trait.BitCollection.html#method.runs).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Runs<C: BitCollection> {
+    // Holds the other fields of each run.
+    collection: C,
+    bits: C::Bits,
+}
+
+impl<C: BitCollection> Runs<C> {
+    #[inline]
+    pub(crate) fn new(collection: &C) -> Self {
+        let bits = collection.bits();
+        // `bits` are those of `collection`, which is valid; the copy is only
+        // kept for its other fields.
+        let collection = unsafe { collection.with_bits(bits) };
+        Runs { collection, bits }
+    }
+
+    /// Removes `run` from the bits left and returns it as a collection.
+    #[inline]
+    fn take(&mut self, run: C::Bits) -> C {
+        self.bits ^= run;
+        // `run` is a run of bits set in `self.bits`, which starts as the bits
+        // of the collection and only has runs cleared from it.
+        unsafe { self.collection.with_bits(run) }
+    }
+}
+
+/// Returns the lowest `n` bits set, where `n` may be up to `W::BITS`.
+#[inline]
+pub(crate) fn low_bits<W: Word>(n: u32) -> W {
+    if n >= W::BITS {
+        !W::ZERO
+    } else {
+        (W::ONE << n).wrapping_sub(W::ONE)
+    }
+}
+
+impl<C: BitCollection> Iterator for Runs<C> {
+    type Item = C;
+
+    #[inline]
+    fn next(&mut self) -> Option<C> {
+        if self.bits == Word::ZERO {
+            return None;
+        }
+        let start = self.bits.trailing_zeros();
+        let len = (!(self.bits >> start)).trailing_zeros();
+        let run = low_bits::<C::Bits>(len) << start;
+        Some(self.take(run))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<C: BitCollection> DoubleEndedIterator for Runs<C> {
+    #[inline]
+    fn next_back(&mut self) -> Option<C> {
+        if self.bits == Word::ZERO {
+            return None;
+        }
+        let end = self.bits.leading_zeros();
+        let len = (!(self.bits << end)).leading_zeros();
+        let run = low_bits::<C::Bits>(len) << (<C::Bits as Word>::BITS - end - len);
+        Some(self.take(run))
+    }
+}
+
+impl<C: BitCollection> ExactSizeIterator for Runs<C> {
+    #[inline]
+    fn len(&self) -> usize {
+        // Each run starts with a bit whose lower neighbor is not set
+        (self.bits & !(self.bits << 1)).count_ones() as usize
+    }
+}
+
+impl<C: BitCollection> FusedIterator for Runs<C> {}
@@ -0,0 +1,194 @@
+//! Property testing support for [`BitCollection`](../trait.BitCollection.html)
+//! types.
+//!
+//! This module is available with the `testing` feature. It provides:
+//!
+//! - A [proptest] [`strategy`](fn.strategy.html) for any collection, as well
+//!   as `proptest::arbitrary::Arbitrary` for
+//!   [`BitIter`](../struct.BitIter.html).
+//!
+//! - [quickcheck] `Arbitrary` for [`BitIter`](../struct.BitIter.html).
+//!
+//! - [`check_bit_collection_laws`](fn.check_bit_collection_laws.html), which
+//!   checks that a type behaves like a set of its items.
+//!
+//! Generated collections only ever contain items of
+//! [`FULL`](../trait.BitCollection.html#associatedconstant.FULL).
+//!
+//! [proptest]: https://docs.rs/proptest
+//! [quickcheck]: https://docs.rs/quickcheck
+
+use std::fmt::Debug;
+
+use proptest::arbitrary::Arbitrary as PropArbitrary;
+use proptest::collection::{self, VecStrategy};
+use proptest::strategy::{Map, Strategy};
+use proptest::test_runner::{TestCaseError, TestRunner};
+use quickcheck::{Arbitrary as QuickArbitrary, Gen};
+
+use {BitCollection, BitIter, Quantity};
+
+/// The proptest strategy returned by [`strategy`](fn.strategy.html).
+pub type CollectionStrategy<C> =
+    Map<VecStrategy<::proptest::bool::Any>, fn(Vec<bool>) -> C>;
+
+/// Returns a proptest strategy that generates subsets of
+/// [`FULL`](../trait.BitCollection.html#associatedconstant.FULL).
+///
+/// Values shrink by removing items.
+pub fn strategy<C: BitCollection + Debug>() -> CollectionStrategy<C> {
+    collection::vec(::proptest::bool::ANY, C::FULL.len())
+        .prop_map(from_flags::<C>)
+}
+
+/// Collects the items of `FULL` whose flag is set.
+fn from_flags<C: BitCollection>(flags: Vec<bool>) -> C {
+    C::FULL.into_iter()
+        .zip(flags)
+        .filter(|&(_, set)| set)
+        .map(|(item, _)| item)
+        .collect()
+}
+
+impl<C: BitCollection + Debug> PropArbitrary for BitIter<C> {
+    type Parameters = ();
+    type Strategy = Map<CollectionStrategy<C>, fn(C) -> BitIter<C>>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        strategy::<C>().prop_map(BitIter)
+    }
+}
+
+impl<C> QuickArbitrary for BitIter<C>
+    where C: BitCollection + Clone + Send + 'static
+{
+    fn arbitrary(g: &mut Gen) -> Self {
+        let flags = (0..C::FULL.len())
+            .map(|_| <bool as QuickArbitrary>::arbitrary(g))
+            .collect();
+        BitIter(from_flags(flags))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item=Self>> {
+        let bits = self.0.clone();
+        Box::new(self.0.clone().into_iter().map(move |item| {
+            BitIter(bits.clone().removing(item))
+        }))
+    }
+}
+
+macro_rules! law {
+    ($cond:expr, $($arg:tt)+) => {
+        if !$cond {
+            return Err(TestCaseError::fail(format!($($arg)+)));
+        }
+    }
+}
+
+/// Checks that `C` behaves like a set of its items, panicking with a
+/// minimal counterexample otherwise.
+///
+/// This generates pairs of collections and checks that:
+///
+/// - De Morgan's laws hold for `!`, `&` and `|`, and `-` removes items.
+/// - [`len`] matches the number of items iterated over.
+/// - [`lsb`] and [`msb`] are the first and last items iterated over, and
+///   [`select`] and [`rank`] agree with iteration order.
+/// - [`into_bit`] only returns an item when [`len`] is 1.
+/// - [`quantity`] matches [`len`].
+///
+/// # Examples
+///
+/// ```
+/// # extern crate bit_collection;
+/// # use bit_collection::*;
+/// #[derive(Copy, Clone, Debug, PartialEq)]
+/// enum Color { Red, Green, Blue }
+///
+/// #[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+/// #[bit(Color, mask = "0b111")]
+/// struct Colors(u8);
+///
+/// # fn main() {
+/// bit_collection::testing::check_bit_collection_laws::<Colors>();
+/// # }
+/// ```
+///
+/// [`len`]: ../trait.BitCollection.html#tymethod.len
+/// [`lsb`]: ../trait.BitCollection.html#method.lsb
+/// [`msb`]: ../trait.BitCollection.html#method.msb
+/// [`select`]: ../trait.BitCollection.html#method.select
+/// [`rank`]: ../trait.BitCollection.html#method.rank
+/// [`into_bit`]: ../trait.BitCollection.html#method.into_bit
+/// [`quantity`]: ../trait.BitCollection.html#method.quantity
+pub fn check_bit_collection_laws<C>()
+    where
+        C: BitCollection + Clone + Debug + PartialEq,
+        C::Item: Clone + Debug + PartialEq,
+{
+    let pairs = (strategy::<C>(), strategy::<C>());
+    let result = TestRunner::default().run(&pairs, |(a, b)| {
+        check_algebra(a.clone(), b.clone())?;
+        check_iteration(a)?;
+        check_iteration(b)
+    });
+    if let Err(error) = result {
+        panic!("{}", error);
+    }
+}
+
+fn check_algebra<C>(a: C, b: C) -> Result<(), TestCaseError>
+    where C: BitCollection + Clone + Debug + PartialEq
+{
+    let union = a.clone() | b.clone();
+    let intersection = a.clone() & b.clone();
+
+    law!(!union.clone() == !a.clone() & !b.clone(),
+         "!({:?} | {:?}) != !a & !b", a, b);
+    law!(!intersection.clone() == !a.clone() | !b.clone(),
+         "!({:?} & {:?}) != !a | !b", a, b);
+    law!(a.clone() - b.clone() == a.clone() & !b.clone(),
+         "{:?} - {:?} != a & !b", a, b);
+    law!(union.contains(a.clone()) && a.contains(intersection.clone()),
+         "{:?} | {:?} does not contain a, which does not contain a & b", a, b);
+    law!(C::FULL.contains(union.clone()) && !C::FULL == C::EMPTY,
+         "FULL does not contain {:?} | {:?}, or !FULL is not empty", a, b);
+    law!(union.len() + intersection.len() == a.len() + b.len(),
+         "len of {:?} and {:?} does not add up", a, b);
+    Ok(())
+}
+
+fn check_iteration<C>(a: C) -> Result<(), TestCaseError>
+    where
+        C: BitCollection + Clone + Debug + PartialEq,
+        C::Item: Clone + Debug + PartialEq,
+{
+    let len = a.len();
+    let mut count = 0;
+    for (n, item) in a.clone().into_iter().enumerate() {
+        law!(a.select(n) == Some(item.clone()),
+             "select({}) of {:?} is not {:?}", n, a, item);
+        law!(a.rank(item.clone()) == n,
+             "rank of {:?} in {:?} is not {}", item, a, n);
+        count += 1;
+    }
+    law!(count == len, "len of {:?} is {}, but iterated {} items", a, len, count);
+    law!(a.is_empty() == (len == 0), "is_empty of {:?} does not match len", a);
+
+    let mut iter = a.clone().into_iter();
+    law!(a.lsb() == iter.next(), "lsb of {:?} is not its first item", a);
+    let mut iter = a.clone().into_iter();
+    law!(a.msb() == iter.next_back(), "msb of {:?} is not its last item", a);
+
+    let bit = a.clone().into_bit();
+    law!(bit.is_some() == (len == 1) && (bit.is_none() || bit == a.lsb()),
+         "into_bit of {:?} is {:?}", a, bit);
+
+    let quantity = match len {
+        0 => Quantity::None,
+        1 => Quantity::Single,
+        _ => Quantity::Multiple,
+    };
+    law!(a.quantity() == quantity, "quantity of {:?} is not {:?}", a, quantity);
+    Ok(())
+}
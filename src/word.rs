@@ -0,0 +1,334 @@
+//! Primitive integers that back a [`BitCollection`](../trait.BitCollection.html).
+//!
+//! # Hardware Acceleration
+//!
+//! When the `bmi2` feature is enabled and the crate is compiled for an
+//! `x86_64` target with the `bmi1` and `bmi2` target features (e.g. with
+//! `-C target-cpu=native`), [`Word`](trait.Word.html) operations use the
+//! `blsr`, `blsi`, `pdep` and `pext` instructions. Otherwise they use the
+//! portable implementations in [`soft`](soft/index.html), which always give
+//! the same results.
+//!
+//! The feature is opt-in because `pdep` and `pext` are microcoded, and thus
+//! slow, on some processors that support them.
+//!
+//! The implementation is chosen at compile time; there is no runtime
+//! detection. A binary built without those target features uses the portable
+//! implementations, even on a processor that supports the instructions.
+
+use core::fmt::Debug;
+use core::hash::Hash;
+use core::ops;
+
+/// A primitive unsigned integer that holds the bits of a collection.
+pub trait Word: Copy + Eq + Ord + Hash + Debug
+    + ops::Not<Output=Self>
+    + ops::BitAnd<Output=Self>
+    + ops::BitAndAssign
+    + ops::BitOr<Output=Self>
+    + ops::BitOrAssign
+    + ops::BitXor<Output=Self>
+    + ops::BitXorAssign
+    + ops::Shl<u32, Output=Self>
+    + ops::Shr<u32, Output=Self>
+{
+    /// The value with no bits set.
+    const ZERO: Self;
+
+    /// The value with only the least significant bit set.
+    const ONE: Self;
+
+    /// The number of bits in `Self`.
+    const BITS: u32;
+
+    /// Returns the number of bits set.
+    fn count_ones(self) -> u32;
+
+    /// Returns the number of unset bits below the least significant set bit.
+    fn trailing_zeros(self) -> u32;
+
+    /// Returns the number of unset bits above the most significant set bit.
+    fn leading_zeros(self) -> u32;
+
+    /// Returns `self - rhs`, wrapping around on overflow.
+    fn wrapping_sub(self, rhs: Self) -> Self;
+
+    /// Returns `self` with its least significant set bit cleared.
+    fn clear_lowest(self) -> Self;
+
+    /// Returns only the least significant set bit of `self`.
+    fn isolate_lowest(self) -> Self;
+
+    /// Returns only the most significant set bit of `self`.
+    #[inline]
+    fn isolate_highest(self) -> Self {
+        // `BITS` is a power of two, so the mask takes zero leading zeros to
+        // the top bit and `BITS` leading zeros to the top bit of `ZERO`.
+        let top = Self::ONE << (Self::BITS - 1);
+        (top >> (self.leading_zeros() & (Self::BITS - 1))) & self
+    }
+
+    /// Returns only the `n`th least significant set bit of `self`, or zero if
+    /// `self` does not have more than `n` bits set.
+    fn select(self, n: u32) -> Self;
+
+    /// Deposits the low bits of `self` into the positions of the set bits of
+    /// `mask`, from least to most significant.
+    fn pdep(self, mask: Self) -> Self;
+
+    /// Extracts the bits of `self` at the positions of the set bits of `mask`
+    /// into the low bits of the result, from least to most significant.
+    fn pext(self, mask: Self) -> Self;
+}
+
+/// Portable implementations of [`Word`](../trait.Word.html) operations.
+///
+/// These do not use any special instructions, and are what `Word` falls back
+/// to without hardware acceleration.
+pub mod soft {
+    use super::Word;
+
+    /// Returns `x` with its least significant set bit cleared.
+    #[inline]
+    pub fn clear_lowest<W: Word>(x: W) -> W {
+        x & x.wrapping_sub(W::ONE)
+    }
+
+    /// Returns only the least significant set bit of `x`.
+    #[inline]
+    pub fn isolate_lowest<W: Word>(x: W) -> W {
+        x & W::ZERO.wrapping_sub(x)
+    }
+
+    /// Returns only the `n`th least significant set bit of `x`, or zero if `x`
+    /// does not have more than `n` bits set.
+    #[inline]
+    pub fn select<W: Word>(mut x: W, n: u32) -> W {
+        if n >= x.count_ones() {
+            return W::ZERO;
+        }
+        for _ in 0..n {
+            x = clear_lowest(x);
+        }
+        isolate_lowest(x)
+    }
+
+    /// Deposits the low bits of `x` into the positions of the set bits of
+    /// `mask`.
+    #[inline]
+    pub fn pdep<W: Word>(x: W, mut mask: W) -> W {
+        let mut result = W::ZERO;
+        let mut bit = W::ONE;
+        while mask != W::ZERO {
+            let low = isolate_lowest(mask);
+            if x & bit != W::ZERO {
+                result |= low;
+            }
+            mask ^= low;
+            bit = bit << 1;
+        }
+        result
+    }
+
+    /// Extracts the bits of `x` at the positions of the set bits of `mask`.
+    #[inline]
+    pub fn pext<W: Word>(x: W, mut mask: W) -> W {
+        let mut result = W::ZERO;
+        let mut bit = W::ONE;
+        while mask != W::ZERO {
+            let low = isolate_lowest(mask);
+            if x & low != W::ZERO {
+                result |= bit;
+            }
+            mask ^= low;
+            bit = bit << 1;
+        }
+        result
+    }
+}
+
+#[cfg(all(feature = "bmi2", target_arch = "x86_64",
+          target_feature = "bmi1", target_feature = "bmi2"))]
+mod imp {
+    use core::arch::x86_64::*;
+
+    macro_rules! impl_hard {
+        ($t:ty, $blsr:ident, $blsi:ident, $pdep:ident, $pext:ident) => {
+            #[inline]
+            pub fn clear_lowest(x: $t) -> $t { unsafe { $blsr(x) } }
+
+            #[inline]
+            pub fn isolate_lowest(x: $t) -> $t { unsafe { $blsi(x) } }
+
+            #[inline]
+            pub fn select(x: $t, n: u32) -> $t {
+                if n < 8 * ::core::mem::size_of::<$t>() as u32 {
+                    pdep(1 << n, x)
+                } else {
+                    0
+                }
+            }
+
+            #[inline]
+            pub fn pdep(x: $t, mask: $t) -> $t { unsafe { $pdep(x, mask) } }
+
+            #[inline]
+            pub fn pext(x: $t, mask: $t) -> $t { unsafe { $pext(x, mask) } }
+        }
+    }
+
+    pub mod w32 {
+        use super::*;
+        impl_hard!(u32, _blsr_u32, _blsi_u32, _pdep_u32, _pext_u32);
+    }
+
+    pub mod w64 {
+        use super::*;
+        impl_hard!(u64, _blsr_u64, _blsi_u64, _pdep_u64, _pext_u64);
+    }
+}
+
+#[cfg(not(all(feature = "bmi2", target_arch = "x86_64",
+              target_feature = "bmi1", target_feature = "bmi2")))]
+mod imp {
+    macro_rules! impl_soft {
+        ($m:ident, $t:ident) => {
+            pub mod $m {
+                use word::soft;
+
+                #[inline]
+                pub fn clear_lowest(x: $t) -> $t { soft::clear_lowest(x) }
+
+                #[inline]
+                pub fn isolate_lowest(x: $t) -> $t { soft::isolate_lowest(x) }
+
+                #[inline]
+                pub fn select(x: $t, n: u32) -> $t { soft::select(x, n) }
+
+                #[inline]
+                pub fn pdep(x: $t, mask: $t) -> $t { soft::pdep(x, mask) }
+
+                #[inline]
+                pub fn pext(x: $t, mask: $t) -> $t { soft::pext(x, mask) }
+            }
+        }
+    }
+
+    impl_soft!(w32, u32);
+    impl_soft!(w64, u64);
+}
+
+macro_rules! impl_word {
+    ($($t:ty => $imp:ident, $w:ty;)+) => { $(
+        impl Word for $t {
+            const ZERO: Self = 0;
+
+            const ONE: Self = 1;
+
+            const BITS: u32 = (::core::mem::size_of::<$t>() * 8) as u32;
+
+            #[inline]
+            fn count_ones(self) -> u32 { self.count_ones() }
+
+            #[inline]
+            fn trailing_zeros(self) -> u32 { self.trailing_zeros() }
+
+            #[inline]
+            fn leading_zeros(self) -> u32 { self.leading_zeros() }
+
+            #[inline]
+            fn wrapping_sub(self, rhs: Self) -> Self { self.wrapping_sub(rhs) }
+
+            #[inline]
+            fn clear_lowest(self) -> Self {
+                imp::$imp::clear_lowest(self as $w) as $t
+            }
+
+            #[inline]
+            fn isolate_lowest(self) -> Self {
+                imp::$imp::isolate_lowest(self as $w) as $t
+            }
+
+            #[inline]
+            fn select(self, n: u32) -> Self {
+                imp::$imp::select(self as $w, n) as $t
+            }
+
+            #[inline]
+            fn pdep(self, mask: Self) -> Self {
+                imp::$imp::pdep(self as $w, mask as $w) as $t
+            }
+
+            #[inline]
+            fn pext(self, mask: Self) -> Self {
+                imp::$imp::pext(self as $w, mask as $w) as $t
+            }
+        }
+    )+ }
+}
+
+impl_word! {
+    u8  => w32, u32;
+    u16 => w32, u32;
+    u32 => w32, u32;
+    u64 => w64, u64;
+}
+
+#[cfg(any(target_pointer_width = "16", target_pointer_width = "32"))]
+impl_word! { usize => w32, u32; }
+
+#[cfg(target_pointer_width = "64")]
+impl_word! { usize => w64, u64; }
+
+impl Word for u128 {
+    const ZERO: Self = 0;
+
+    const ONE: Self = 1;
+
+    const BITS: u32 = 128;
+
+    #[inline]
+    fn count_ones(self) -> u32 { self.count_ones() }
+
+    #[inline]
+    fn trailing_zeros(self) -> u32 { self.trailing_zeros() }
+
+    #[inline]
+    fn leading_zeros(self) -> u32 { self.leading_zeros() }
+
+    #[inline]
+    fn wrapping_sub(self, rhs: Self) -> Self { self.wrapping_sub(rhs) }
+
+    #[inline]
+    fn clear_lowest(self) -> Self { soft::clear_lowest(self) }
+
+    #[inline]
+    fn isolate_lowest(self) -> Self { soft::isolate_lowest(self) }
+
+    #[inline]
+    fn select(self, n: u32) -> Self {
+        let (lo, hi) = (self as u64, (self >> 64) as u64);
+        let lo_len = lo.count_ones();
+        if n < lo_len {
+            lo.select(n) as u128
+        } else {
+            (hi.select(n - lo_len) as u128) << 64
+        }
+    }
+
+    #[inline]
+    fn pdep(self, mask: Self) -> Self {
+        let (lo, hi) = (mask as u64, (mask >> 64) as u64);
+        let lo_bits = (self as u64).pdep(lo) as u128;
+        let hi_bits = ((self >> lo.count_ones()) as u64).pdep(hi) as u128;
+        lo_bits | hi_bits << 64
+    }
+
+    #[inline]
+    fn pext(self, mask: Self) -> Self {
+        let (lo, hi) = (mask as u64, (mask >> 64) as u64);
+        let lo_bits = (self as u64).pext(lo) as u128;
+        let hi_bits = ((self >> 64) as u64).pext(hi) as u128;
+        lo_bits | hi_bits << lo.count_ones()
+    }
+}
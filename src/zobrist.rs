@@ -0,0 +1,107 @@
+use core::marker::PhantomData;
+
+use BitCollection;
+
+/// The largest number of items a collection can have, which is the number of
+/// bits in `u128`.
+const MAX_ITEMS: usize = 128;
+
+/// The seed used by [`ZobristTable::new`](struct.ZobristTable.html#method.new).
+const DEFAULT_SEED: u64 = 0x2545_f491_4f6c_dd1d;
+
+/// A table of random keys for [Zobrist hashing][zobrist] the contents of a
+/// [`BitCollection`](trait.BitCollection.html).
+///
+/// Each item in [`FULL`] is given its own `u64` key, and a collection hashes to
+/// the XOR of the keys of its items. Keys are generated deterministically from
+/// a seed, so tables are reproducible across runs and do not require `std`.
+///
+/// # Examples
+///
+/// ```
+/// # include!("../templates/imports.rs");
+/// # include!("../templates/castle_rights.rs");
+/// # fn main() {
+/// let table = ZobristTable::<CastleRights>::new();
+///
+/// let mut rights = CastleRights::FULL;
+/// let mut hash = table.hash(&rights);
+///
+/// rights.remove(CastleRight::WhiteKingside);
+/// hash = table.update(hash, CastleRights::EMPTY, CastleRight::WhiteKingside);
+///
+/// assert_eq!(hash, table.hash(&rights));
+/// # }
+/// ```
+///
+/// [zobrist]: https://chessprogramming.wikispaces.com/Zobrist+Hashing
+/// [`FULL`]: trait.BitCollection.html#associatedconstant.FULL
+pub struct ZobristTable<C> {
+    keys: [u64; MAX_ITEMS],
+    marker: PhantomData<C>,
+}
+
+impl<C> Clone for ZobristTable<C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ZobristTable { keys: self.keys, marker: PhantomData }
+    }
+}
+
+impl<C: BitCollection> Default for ZobristTable<C> {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
+
+impl<C: BitCollection> ZobristTable<C> {
+    /// Creates a table with keys generated from a fixed seed.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_seed(DEFAULT_SEED)
+    }
+
+    /// Creates a table with keys generated from `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        let mut state = seed;
+        let mut keys = [0; MAX_ITEMS];
+        for key in keys.iter_mut().take(C::FULL.len()) {
+            *key = splitmix64(&mut state);
+        }
+        ZobristTable { keys, marker: PhantomData }
+    }
+
+    /// Returns the key for `item`.
+    #[inline]
+    pub fn key(&self, item: C::Item) -> u64 {
+        self.keys[C::FULL.rank(item)]
+    }
+
+    /// Returns the hash of the items in `collection`.
+    #[inline]
+    pub fn hash<T: Into<C>>(&self, collection: T) -> u64 {
+        collection.into().into_iter().fold(0, |hash, item| hash ^ self.key(item))
+    }
+
+    /// Returns `hash` updated with the items of `added` inserted and the items
+    /// of `removed` removed.
+    ///
+    /// The items of `added` must not already be part of `hash`, and the items
+    /// of `removed` must be.
+    #[inline]
+    pub fn update<A, R>(&self, hash: u64, added: A, removed: R) -> u64
+        where A: Into<C>, R: Into<C>
+    {
+        hash ^ self.hash(added) ^ self.hash(removed)
+    }
+}
+
+/// The SplitMix64 generator, which produces well-distributed keys from any
+/// seed, including zero.
+#[inline]
+pub(crate) fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
@@ -6,6 +6,6 @@ enum CastleRight {
     BlackQueenside,
 }
 
+#[derive(BitCollection, Copy, Clone)]
 #[bit(CastleRight, mask = "0b1111")]
-#[derive(BitCollection)]
 struct CastleRights(u8);
@@ -0,0 +1,43 @@
+extern crate bit_collection;
+extern crate core;
+
+mod common;
+
+use bit_collection::*;
+use common::{Square, Squares};
+
+fn even(s: Square) -> bool { s.0 & 1 == 0 }
+
+#[test]
+fn filter() {
+    let all = Squares::FULL;
+    let expected: Squares = all.into_iter().filter(|&s| even(s)).collect();
+
+    let mut retained = all;
+    retained.retain(even);
+    assert_eq!(retained, expected);
+    assert_eq!(all.filtered(even), expected);
+
+    let (evens, odds) = all.partition(even);
+    assert_eq!(evens, expected);
+    assert_eq!(odds, all - expected);
+    assert_eq!(Squares::EMPTY.partition(even), (Squares::EMPTY, Squares::EMPTY));
+}
+
+#[test]
+fn take() {
+    let all = Squares::FULL;
+    for n in 0..=all.len() + 1 {
+        let (first, rest) = all.take_n(n);
+        let expected: Squares = all.into_iter().take(n).collect();
+        assert_eq!(first, expected);
+        assert_eq!(rest, all - expected);
+    }
+    assert_eq!(all.take_n(1000), (all, Squares::EMPTY));
+
+    let (low, high) = all.take_while(|s| s.0 < 20);
+    assert_eq!(low.msb(), Some(Square(15)));
+    assert_eq!(high.lsb(), Some(Square(40)));
+    assert_eq!(low | high, all);
+    assert_eq!(all.take_while(|_| true), (all, Squares::EMPTY));
+}
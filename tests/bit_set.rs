@@ -0,0 +1,64 @@
+#[macro_use]
+extern crate bit_collection;
+extern crate core;
+
+mod perms {
+    bit_set! {
+        /// A file permission.
+        pub enum Perm { Read, Write, Exec }
+
+        /// A set of file permissions.
+        pub struct Perms: u8;
+    }
+
+    bit_set! {
+        pub enum Byte { B0, B1, B2, B3, B4, B5, B6, B7, }
+        pub struct Bytes: u8;
+    }
+}
+
+// Glob imports of names that are also in this crate must not be ambiguous.
+mod shadowed {
+    mod names {
+        pub struct BitIter;
+        pub struct Word;
+    }
+
+    use self::names::*;
+
+    bit_set! {
+        pub enum Flag { A, B }
+        pub struct Flags: u8;
+    }
+
+    pub fn names() -> (BitIter, Word, Flags) {
+        (BitIter, Word, Flags::A)
+    }
+}
+
+use bit_collection::BitCollection;
+use perms::*;
+
+#[test]
+fn mask() {
+    assert_eq!(Perms::FULL.bits(), 0b111);
+    assert_eq!(Bytes::FULL.bits(), !0);
+    assert_eq!((!Perms::Read).bits(), 0b110);
+}
+
+#[test]
+fn constants() {
+    assert_eq!(Perms::Read, Perms::from(Perm::Read));
+    assert_eq!(Perms::Exec.bits(), 0b100);
+    assert_eq!(Bytes::B7.into_bit(), Some(Byte::B7));
+
+    let perms = Perms::Write | Perm::Exec;
+    assert_eq!(perms.into_iter().collect::<Vec<_>>(), [Perm::Write, Perm::Exec]);
+    assert_eq!(perms.msb(), Some(Perm::Exec));
+}
+
+#[test]
+fn no_imports() {
+    let (_, _, flags) = shadowed::names();
+    assert_eq!(flags.len(), 1);
+}
@@ -0,0 +1,43 @@
+extern crate bit_collection;
+extern crate core;
+
+use bit_collection::*;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Flag { A, B, C }
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Flag, mask = "0b111", bitflags_compat)]
+struct Flags(u8);
+
+const AB: Flags = Flags(0b011);
+
+#[test]
+fn constructors() {
+    const ALL: Flags = Flags::all();
+
+    assert_eq!(ALL, Flags::FULL);
+    assert_eq!(Flags::empty(), Flags::EMPTY);
+    assert_eq!(Flags::from_bits(0b101), Some(Flags::from(Flag::A) | Flag::C));
+    assert_eq!(Flags::from_bits(0b1000), None);
+    assert_eq!(Flags::from_bits_truncate(0b1110).bits(), 0b110);
+    assert!(Flags::all().is_all() && !AB.is_all());
+    assert!(Flags::empty().is_empty() && !AB.is_empty());
+}
+
+#[test]
+fn mutation() {
+    let mut flags = Flags::empty();
+    flags.insert(AB);
+    flags.remove(Flag::A);
+    assert!(flags.contains(Flag::B) && !flags.contains(AB));
+    assert!(flags.intersects(AB));
+
+    flags.toggle(Flags::all());
+    assert_eq!(flags.bits(), 0b101);
+
+    flags.set(Flag::B, true);
+    flags.set(Flag::C, false);
+    assert_eq!(flags, AB);
+    assert_eq!(flags.into_iter().collect::<Vec<_>>(), [Flag::A, Flag::B]);
+}
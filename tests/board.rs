@@ -0,0 +1,67 @@
+extern crate bit_collection;
+extern crate core;
+
+use bit_collection::*;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct Square(u8);
+
+#[derive(BitCollection)]
+#[bit(Square, retr = "0")]
+struct Bitboard(u64);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct Slot(u8);
+
+#[derive(BitCollection, Debug, PartialEq)]
+#[bit(Slot, mask = "0b111111", retr = "0")]
+struct Slots(u8);
+
+const KNIGHTS: &str = "\
+8 . X . . . . X .
+7 . . . . . . . .
+6 . . . . . . . .
+5 . . . . . . . .
+4 . . . . . . . .
+3 . . . . . . . .
+2 . . . . . . . .
+1 . X . . . . X .
+  a b c d e f g h
+";
+
+#[test]
+fn display_square() {
+    let knights = Bitboard(0x4200000000000042);
+    let board = BoardDisplay::new(&knights).unwrap();
+    assert_eq!((board.width(), board.height()), (8, 8));
+    assert_eq!(board.to_string(), KNIGHTS);
+}
+
+#[test]
+fn display_width() {
+    let slots = Slots::from(Slot(0)) | Slot(5);
+    assert!(BoardDisplay::new(&slots).is_none());
+    assert!(BoardDisplay::with_width(&slots, 4).is_none());
+
+    let board = BoardDisplay::with_width(&slots, 3).unwrap();
+    assert_eq!(board.to_string(), "2 . . X\n1 X . .\n  a b c\n");
+}
+
+#[test]
+fn parse_round_trip() {
+    let knights: Bitboard = parse_board(KNIGHTS).unwrap();
+    assert_eq!(knights.0, 0x4200000000000042);
+
+    let slots: Slots = parse_board("x . .\n. . X").unwrap();
+    assert_eq!(slots.0, 0b001100);
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(parse_board::<Slots>("X . ?\n. . ."),
+               Err(ParseBoardError::InvalidCell('?')));
+    assert_eq!(parse_board::<Slots>("X . .\n. ."),
+               Err(ParseBoardError::UnevenRanks));
+    assert_eq!(parse_board::<Slots>("X . .\n. . .\n. . ."),
+               Err(ParseBoardError::CellCount(9)));
+}
@@ -0,0 +1,36 @@
+extern crate bit_collection;
+extern crate core;
+
+use bit_collection::*;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Cap(u8);
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Cap, mask = "0x00ff_ff01", retr = "0")]
+struct Caps(u32);
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Cap, retr = "0")]
+struct Flags(u8);
+
+#[test]
+fn to_bytes() {
+    let caps = Caps::from(Cap(0)) | Cap(9) | Cap(23);
+    assert_eq!(caps.to_le_bytes(), [0x01, 0x02, 0x80, 0x00]);
+    assert_eq!(caps.to_be_bytes(), [0x00, 0x80, 0x02, 0x01]);
+    assert_eq!(Flags::FULL.to_le_bytes(), [0xff]);
+}
+
+#[test]
+fn from_bytes() {
+    let caps = Caps::from(Cap(8)) | Cap(16);
+    assert_eq!(Caps::from_le_bytes([0x00, 0x01, 0x01, 0x00]), Some(caps));
+    assert_eq!(Caps::from_be_bytes([0x00, 0x01, 0x01, 0x00]), Some(caps));
+    assert_eq!(Caps::from_le_bytes([0x02, 0x01, 0x01, 0x00]), None);
+    assert_eq!(Caps::from_be_bytes([0x01, 0x01, 0x01, 0x00]), None);
+
+    assert_eq!(Caps::from_le_bytes_truncate([0x02, 0x01, 0x01, 0xff]), caps);
+    assert_eq!(Caps::from_be_bytes_truncate([0xff, 0x01, 0x01, 0x00]), caps);
+    assert_eq!(Caps::from_le_bytes(caps.to_le_bytes()), Some(caps));
+}
@@ -0,0 +1,24 @@
+//! Fixtures shared between the tests that declare `mod common`.
+
+#![allow(dead_code)]
+
+use bit_collection::*;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Square(pub u8);
+
+/// Squares of a board with gaps both within and between its bytes.
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Square, mask = "0x00ff_ff00_0000_ff0f", retr = "0")]
+pub struct Squares(pub u64);
+
+/// Returns deterministic values from an xorshift generator starting at
+/// `state`, which must not be zero.
+pub fn xorshift(mut state: u64) -> impl Iterator<Item = u64> {
+    (0..).map(move |_: u64| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    })
+}
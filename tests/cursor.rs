@@ -0,0 +1,75 @@
+extern crate bit_collection;
+extern crate core;
+
+use bit_collection::*;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Worker(u8);
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Worker, mask = "0b1111_1110", retr = "0")]
+struct Workers(u8);
+
+fn workers(bits: u8) -> Workers {
+    Workers::from(bits)
+}
+
+#[test]
+fn successors() {
+    for ready in (0..=u8::MAX).map(workers) {
+        let items: Vec<u8> = ready.into_iter().map(|w| w.0).collect();
+        for i in 0..8 {
+            let after = items.iter().cloned().find(|&x| x > i);
+            let before = items.iter().cloned().rev().find(|&x| x < i);
+            assert_eq!(ready.next_after(Worker(i)).map(|w| w.0), after);
+            assert_eq!(ready.prev_before(Worker(i)).map(|w| w.0), before);
+            assert_eq!(ready.next_cyclic(Worker(i)).map(|w| w.0),
+                       after.or_else(|| items.first().cloned()));
+            assert_eq!(ready.prev_cyclic(Worker(i)).map(|w| w.0),
+                       before.or_else(|| items.last().cloned()));
+        }
+    }
+    assert_eq!(workers(0b1000).next_cyclic(Worker(3)), Some(Worker(3)));
+}
+
+#[test]
+fn cursor() {
+    let ready = workers(0b1010_0100);
+    let mut cursor = ready.cursor();
+    assert!(cursor.is_ghost());
+    assert_eq!(cursor.current(), None);
+
+    let mut forward = Vec::new();
+    loop {
+        cursor.move_next();
+        match cursor.current() {
+            Some(w) => forward.push(w.0),
+            None => break,
+        }
+    }
+    assert_eq!(forward, [2, 5, 7]);
+    assert!(cursor.is_ghost());
+
+    cursor.move_prev();
+    assert_eq!(cursor.current(), Some(Worker(7)));
+    cursor.move_prev();
+    assert_eq!(cursor.current(), Some(Worker(5)));
+    assert_eq!(cursor.peek_prev(), Some(Worker(2)));
+    assert_eq!(cursor.peek_next(), Some(Worker(7)));
+
+    cursor.seek(Worker(3));
+    assert_eq!(cursor.current(), None);
+    assert!(!cursor.is_ghost());
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(Worker(5)));
+
+    cursor.seek(Worker(2));
+    cursor.move_prev();
+    assert!(cursor.is_ghost());
+    cursor.move_prev();
+    assert_eq!(cursor.current(), Some(Worker(7)));
+
+    cursor.reset();
+    assert_eq!(cursor.peek_next(), Some(Worker(2)));
+    assert_eq!(cursor.collection(), &ready);
+}
@@ -0,0 +1,58 @@
+extern crate bit_collection;
+extern crate core;
+
+mod common;
+
+use bit_collection::*;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct Square(u8);
+
+#[derive(BitCollection, Copy, Clone, Debug, Eq, PartialEq)]
+#[bit(Square, retr = "0")]
+struct Bitboard(u64);
+
+const FILE_A: Bitboard = Bitboard(0x0101_0101_0101_0101);
+
+#[test]
+fn extract_deposit() {
+    let occupied = Bitboard(0x0001_0000_0100_0001);
+    let index = occupied.extract(FILE_A);
+    assert_eq!(index, 0b100_1001);
+    assert_eq!(Bitboard::deposit(index, FILE_A), occupied);
+
+    // Every subset of the mask survives a round trip
+    for index in 0..256 {
+        let subset = Bitboard::deposit(index, FILE_A);
+        assert!(FILE_A.contains(subset));
+        assert_eq!(subset.len(), index.count_ones() as usize);
+        assert_eq!(subset.extract(FILE_A), index);
+    }
+
+    assert_eq!(Bitboard::deposit(!0, Bitboard::EMPTY), Bitboard::EMPTY);
+    assert_eq!(Bitboard(0xdead_beef).extract(Bitboard::FULL), 0xdead_beef);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn soft_matches_hardware() {
+    use core::arch::x86_64::{_pdep_u64, _pext_u64};
+    use bit_collection::word::soft;
+
+    #[target_feature(enable = "bmi2")]
+    unsafe fn check(x: u64, mask: u64) {
+        assert_eq!(soft::pdep(x, mask), _pdep_u64(x, mask));
+        assert_eq!(soft::pext(x, mask), _pext_u64(x, mask));
+        assert_eq!(x.pdep(mask), _pdep_u64(x, mask));
+        assert_eq!(x.pext(mask), _pext_u64(x, mask));
+    }
+
+    if !is_x86_feature_detected!("bmi2") {
+        return;
+    }
+
+    for x in common::xorshift(0x9e37_79b9_7f4a_7c15).take(1000) {
+        let mask = x.rotate_left(32) & x.rotate_left(7);
+        unsafe { check(x, mask) };
+    }
+}
@@ -1,6 +1,5 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-#[macro_use]
 extern crate bit_collection as bc;
 
 #[cfg(feature = "std")]
@@ -18,7 +17,7 @@ macro_rules! enum_impl {
 
         impl $ident {
             fn all() -> &'static [$ident] {
-                static ALL: &'static [$ident] = &[$($ident::$x),*];
+                static ALL: &[$ident] = &[$($ident::$x),*];
                 ALL
             }
         }
@@ -43,7 +42,7 @@ struct Value4Struct(u8);
 
 impl Value4Struct {
     fn all() -> &'static [Value4Struct] {
-        static ALL: &'static [Value4Struct] = &[
+        static ALL: &[Value4Struct] = &[
             Value4Struct(0), Value4Struct(1), Value4Struct(2), Value4Struct(3)
         ];
         ALL
@@ -55,7 +54,7 @@ struct Value16Struct(u8);
 
 impl Value16Struct {
     fn all() -> &'static [Value16Struct] {
-        static ALL: &'static [Value16Struct] = &[
+        static ALL: &[Value16Struct] = &[
             Value16Struct(0),  Value16Struct(1),  Value16Struct(2),  Value16Struct(3),
             Value16Struct(4),  Value16Struct(5),  Value16Struct(6),  Value16Struct(7),
             Value16Struct(8),  Value16Struct(9),  Value16Struct(10), Value16Struct(11),
@@ -87,12 +86,12 @@ macro_rules! impl_test {
     ($func:ident, $bit:ident, $inner:ty, #[$attr:meta]) => {
         #[test]
         fn $func() {
-            #[$attr]
             #[derive(BitCollection)]
+            #[$attr]
             struct Tuple($inner);
 
-            #[$attr]
             #[derive(BitCollection)]
+            #[$attr]
             struct Struct { bits: $inner }
 
             let all = $bit::all();
@@ -119,3 +118,7 @@ impl_test! {
     bits16_struct, Value16Struct, u16,
     #[bit(Value16Struct, iter = "bc::BitIter", retr = "0")]
 }
+impl_test! {
+    crate_path, Value16Enum, u16,
+    #[bit(Value16Enum, crate = "bc")]
+}
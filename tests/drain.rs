@@ -0,0 +1,67 @@
+extern crate bit_collection;
+extern crate core;
+
+mod common;
+
+use bit_collection::*;
+use common::{Square, Squares};
+
+#[test]
+fn drain() {
+    let mut squares = Squares::FULL;
+    {
+        let mut drain = squares.drain();
+        assert_eq!(drain.len(), Squares::FULL.len());
+        assert_eq!(drain.next(), Some(Square(0)));
+        assert_eq!(drain.next_back(), Some(Square(55)));
+        assert_eq!(drain.len(), Squares::FULL.len() - 2);
+    }
+    assert!(squares.is_empty());
+    assert_eq!(squares.drain().next(), None);
+
+    let mut squares = Squares::FULL;
+    let drained: Squares = squares.drain().collect();
+    assert_eq!(drained, Squares::FULL);
+    assert!(squares.is_empty());
+}
+
+#[test]
+fn drain_range() {
+    let all = Squares::FULL;
+    let in_range = |lo: u8, hi: u8| -> Squares {
+        all.into_iter().filter(|s| lo <= s.0 && s.0 <= hi).collect()
+    };
+
+    let mut squares = all;
+    let drained: Squares = squares.drain_range(Square(2)..Square(17)).collect();
+    assert_eq!(drained, in_range(2, 16));
+    assert_eq!(squares, all - drained);
+
+    let mut squares = all;
+    assert_eq!(squares.drain_range(Square(8)..=Square(15)).next_back(), Some(Square(15)));
+    assert_eq!(squares, all - in_range(8, 15));
+
+    let mut squares = all;
+    squares.drain_range(Square(40)..);
+    assert_eq!(squares, in_range(0, 39));
+
+    let mut squares = all;
+    squares.drain_range(..=Square(0));
+    assert_eq!(squares, all - Square(0));
+
+    let mut squares = all;
+    squares.drain_range(..Square(0));
+    assert_eq!(squares, all);
+
+    let mut squares = all;
+    squares.drain_range(..);
+    assert!(squares.is_empty());
+}
+
+#[test]
+fn as_iter() {
+    let mut squares = Squares::FULL;
+    assert_eq!(squares.as_iter().next(), Some(Square(0)));
+    assert_eq!(squares.as_iter().next_back(), Some(Square(55)));
+    assert_eq!(squares.len(), Squares::FULL.len() - 2);
+}
@@ -0,0 +1,78 @@
+extern crate bit_collection;
+extern crate core;
+
+use bit_collection::*;
+use bit_collection::encoding::{self, DecodeError, EncodeError, MAX_ENCODED_LEN};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Id(u8);
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Id, retr = "0")]
+struct Ids(u128);
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Id, mask = "0xfff0", retr = "0")]
+struct Small(u16);
+
+fn ids(items: &[u8]) -> Ids {
+    items.iter().map(|&i| Id(i)).collect()
+}
+
+#[test]
+fn varint() {
+    let mut buf = [0; 19];
+    let len = encoding::encode_varint(&ids(&[0, 7, 8]), &mut buf).unwrap();
+    assert_eq!(&buf[..len], [0x81, 0x03]);
+    assert_eq!(encoding::encode_varint(&Ids::FULL, &mut buf), Ok(19));
+    assert_eq!(encoding::decode_varint(&buf), Ok(Ids::FULL));
+
+    assert_eq!(encoding::encode_varint(&Ids::EMPTY, &mut buf), Ok(1));
+    assert_eq!(buf[0], 0);
+    assert_eq!(encoding::encode_varint(&Ids::FULL, &mut buf[..4]),
+               Err(EncodeError { needed: 19 }));
+
+    // Empty continuation bytes past the width still overflow
+    let mut padded = [0x80; 20];
+    padded[19] = 0;
+    assert_eq!(encoding::decode_varint::<Ids>(&padded), Err(DecodeError::Overflow));
+    assert_eq!(encoding::decode_varint::<Ids>(&padded[1..]), Ok(Ids::EMPTY));
+}
+
+#[test]
+fn indices() {
+    let mut buf = [0; 128];
+    let set = ids(&[3, 64, 127]);
+    let len = encoding::encode_indices(&set, &mut buf).unwrap();
+    assert_eq!(&buf[..len], [3, 60, 62]);
+    assert_eq!(encoding::decode_indices(&buf[..len]), Ok(set));
+    assert_eq!(encoding::decode_indices::<Ids>(&[127, 0]), Err(DecodeError::Overflow));
+}
+
+#[test]
+fn round_trip() {
+    let sets = [Ids::EMPTY, Ids::FULL, ids(&[127]), ids(&[0, 1, 2]), ids(&[5, 90, 91, 100])];
+    for set in &sets {
+        let mut buf = [0; MAX_ENCODED_LEN];
+        let len = encoding::encode(set, &mut buf).unwrap();
+        assert_eq!(len, encoding::encoded_len(set));
+        assert_eq!(encoding::decode(&buf[..len]), Ok(*set));
+    }
+
+    // Sparse sets of high items use indices
+    let mut buf = [0; MAX_ENCODED_LEN];
+    assert_eq!(encoding::encode(&ids(&[120]), &mut buf), Ok(2));
+    assert_eq!(&buf[..2], [1, 120]);
+}
+
+#[test]
+fn validation() {
+    assert_eq!(encoding::decode::<Small>(&[0, 0x80, 0x02]), Ok(Small::from(Id(8))));
+    assert_eq!(encoding::decode::<Small>(&[0, 0x01]), Err(DecodeError::InvalidItem(0)));
+    assert_eq!(encoding::decode::<Small>(&[1, 16]), Err(DecodeError::Overflow));
+    assert_eq!(encoding::decode::<Small>(&[0, 0x80, 0x80, 0x04]), Err(DecodeError::Overflow));
+    assert_eq!(encoding::decode::<Small>(&[0, 0x80]), Err(DecodeError::Truncated));
+    assert_eq!(encoding::decode::<Small>(&[0, 0x10, 0x00]), Err(DecodeError::TrailingBytes));
+    assert_eq!(encoding::decode::<Small>(&[2]), Err(DecodeError::UnknownFormat(2)));
+    assert_eq!(encoding::decode::<Small>(&[]), Err(DecodeError::Truncated));
+}
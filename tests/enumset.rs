@@ -0,0 +1,53 @@
+#![cfg(feature = "enumset")]
+
+extern crate bit_collection;
+extern crate core;
+extern crate enumset;
+
+use std::convert::TryFrom;
+
+use bit_collection::*;
+use enumset::{EnumSet, EnumSetType};
+
+#[derive(EnumSetType, Debug)]
+enum Perm { Read, Write, Exec, Admin }
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Perm, mask = "0b111", enumset)]
+struct Perms(u8);
+
+#[test]
+fn round_trip() {
+    let perms = Perms::from(Perm::Exec) | Perm::Read;
+    let set = EnumSet::from(perms);
+
+    assert_eq!(set, Perm::Read | Perm::Exec);
+    assert!(set.iter().eq(perms.into_iter()));
+    assert_eq!(Perms::try_from(set), Ok(perms));
+    assert_eq!(EnumSet::from(Perms::EMPTY), EnumSet::empty());
+}
+
+#[test]
+fn outside_full() {
+    let set = Perm::Write | Perm::Admin;
+    assert_eq!(Perms::try_from(set), Err(set));
+    assert_eq!(Perms::from_enum_set(set), None);
+}
+
+#[derive(EnumSetType, Debug)]
+enum Level { L0, L1, L2, L3, L4, L5, L6, L7, L8, L9 }
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Level, mask = "!0", enumset)]
+struct Levels(u8);
+
+#[test]
+fn past_bits() {
+    let set = Level::L0 | Level::L7;
+    assert_eq!(Levels::try_from(set), Ok(Levels::from(Level::L0) | Level::L7));
+
+    for &set in &[Level::L0 | Level::L8, EnumSet::only(Level::L9)] {
+        assert_eq!(Levels::try_from(set), Err(set));
+        assert_eq!(Levels::from_enum_set(set), None);
+    }
+}
@@ -0,0 +1,83 @@
+extern crate bit_collection;
+extern crate core;
+
+use core::marker::PhantomData;
+use bit_collection::*;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Square(u8);
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Square, mask = "0xffff", retr = "0")]
+struct Tracked {
+    #[bit_default = "\"board\""]
+    name: &'static str,
+    generation: u32,
+    #[bit_field]
+    bits: u32,
+    dirty: bool,
+}
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Square, retr = "0")]
+struct Tagged(#[bit_default = "7"] u8, PhantomData<Square>, #[bit_field] u16);
+
+#[test]
+fn defaults() {
+    let full = Tracked::FULL;
+    assert_eq!((full.name, full.generation, full.dirty), ("board", 0, false));
+    assert_eq!(full.len(), 16);
+
+    let from = Tracked::from(Square(3));
+    assert_eq!((from.name, from.bits), ("board", 0b1000));
+
+    let tagged = Tagged::from(Square(15));
+    assert_eq!((tagged.0, tagged.2), (7, 0x8000));
+    assert_eq!(Tagged::EMPTY.0, 7);
+}
+
+#[test]
+fn operators_carry_fields() {
+    let mut board = Tracked::EMPTY;
+    board.generation = 3;
+    board.dirty = true;
+
+    let board = (board | Square(1) | Square(2)) ^ Square(2);
+    assert_eq!(board.generation, 3);
+    assert!(board.dirty);
+    assert_eq!(board.into_iter().collect::<Vec<_>>(), [Square(1)]);
+
+    let board = !(board - Square(1)) & Square(5);
+    assert_eq!((board.generation, board.bits), (3, 0b10_0000));
+
+    let mut board = board;
+    board.insert(Square(0));
+    board.remove(Square(5));
+    assert_eq!((board.generation, board.bits), (3, 1));
+}
+
+#[test]
+fn splits_carry_fields() {
+    let mut board = Tracked::FULL;
+    board.generation = 5;
+    board.dirty = true;
+
+    let (even, odd) = board.partition(|s| s.0 % 2 == 0);
+    assert_eq!((even.generation, odd.generation), (5, 5));
+    assert!(even.dirty && odd.dirty);
+
+    let (first, rest) = board.take_n(3);
+    assert_eq!((first.generation, rest.generation), (5, 5));
+    assert_eq!(rest.len(), 13);
+
+    let (low, high) = board.take_while(|s| s.0 < 4);
+    assert_eq!((low.generation, high.generation), (5, 5));
+
+    let runs: Vec<_> = (board - Square(8)).runs().collect();
+    assert_eq!(runs.len(), 2);
+    assert!(runs.iter().all(|run| run.generation == 5));
+    assert_eq!(board.longest_run().generation, 5);
+    assert_eq!(board.first_run_of_len(4).map(|run| run.generation), Some(5));
+
+    assert_eq!(Tracked::deposit(0b11, board).generation, 5);
+}
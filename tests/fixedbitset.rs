@@ -0,0 +1,39 @@
+#![cfg(feature = "fixedbitset")]
+
+extern crate bit_collection;
+extern crate core;
+extern crate fixedbitset;
+
+use std::convert::TryFrom;
+
+use bit_collection::*;
+use fixedbitset::FixedBitSet;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Square(u8);
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Square, mask = "0xffff_0000_0000_ffff", retr = "0", fixedbitset)]
+struct Squares(u64);
+
+#[test]
+fn round_trip() {
+    let squares: Squares = [Square(63), Square(0), Square(9)].iter().collect();
+    let set = FixedBitSet::from(squares);
+
+    assert_eq!(set.len(), 64);
+    assert!(set.ones().eq(squares.into_iter().map(|s| s.0 as usize)));
+    assert_eq!(Squares::try_from(set), Ok(squares));
+    assert_eq!(FixedBitSet::from(Squares::EMPTY).count_ones(..), 0);
+}
+
+#[test]
+fn outside_full() {
+    let mut set = FixedBitSet::with_capacity(100);
+    set.insert(20);
+    assert_eq!(Squares::try_from(set.clone()), Err(set.clone()));
+
+    set.set(20, false);
+    set.insert(64);
+    assert_eq!(Squares::from_fixed_bit_set(&set), None);
+}
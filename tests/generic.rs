@@ -0,0 +1,52 @@
+extern crate bit_collection;
+extern crate core;
+
+use core::marker::PhantomData;
+use bit_collection::*;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct User;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Group;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Id<T>(u8, PhantomData<T>);
+
+impl<T> Id<T> {
+    fn new(n: u8) -> Self { Id(n, PhantomData) }
+}
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit("Id<T>", retr = "0")]
+struct Ids<T>(u64, PhantomData<T>);
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit("Id<T>", mask = "0xff", retr = "0")]
+struct Tagged<'a, T: 'a = User> where T: Copy {
+    bits: u16,
+    marker: PhantomData<&'a T>,
+}
+
+#[test]
+fn per_item_kind() {
+    let users: Ids<User> = [Id::new(1), Id::new(40)].iter().collect();
+    let groups = Ids::<Group>::from(Id::new(40)) | Id::new(63);
+
+    assert_eq!(users.len(), 2);
+    assert_eq!(users.lsb(), Some(Id::new(1)));
+    assert_eq!(groups.msb(), Some(Id::new(63)));
+    assert!(!(users - Id::new(40)).contains(Id::new(40)));
+    assert_eq!(Ids::<User>::FULL.len(), 64);
+    assert_eq!(Ids::<Group>::EMPTY.into_iter().next(), None);
+}
+
+#[test]
+fn lifetimes_and_where_clauses() {
+    let tagged: Tagged = Tagged::from(0xf0f0) ^ Id::new(0);
+
+    assert_eq!(tagged.len(), 5);
+    assert_eq!(tagged.msb(), Some(Id::new(7)));
+    assert_eq!(!tagged, Tagged::from(0x0f0e));
+    assert_eq!(Tagged::<Group>::FULL.bits, 0xff);
+}
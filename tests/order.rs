@@ -0,0 +1,74 @@
+extern crate bit_collection;
+extern crate core;
+
+mod common;
+
+use bit_collection::*;
+use common::{Square, Squares};
+
+const fn reversed() -> Order<64> {
+    let mut by_rank = [0; 64];
+    let mut i = 0;
+    while i < 64 {
+        by_rank[i] = 63 - i as u8;
+        i += 1;
+    }
+    Order::new(by_rank)
+}
+
+const fn scrambled() -> Order<64> {
+    let mut by_rank = [0; 64];
+    let mut i = 0;
+    while i < 64 {
+        // 37 is coprime with 64, so this is a permutation
+        by_rank[i] = (i * 37 % 64) as u8;
+        i += 1;
+    }
+    Order::new(by_rank)
+}
+
+static REVERSED: Order<64> = reversed();
+static SCRAMBLED: Order<64> = scrambled();
+
+#[test]
+fn ordered() {
+    let squares = Squares::FULL;
+    let forward: Vec<_> = squares.into_iter().collect();
+    let mut backward = forward.clone();
+    backward.reverse();
+
+    assert_eq!(squares.iter_ordered(&REVERSED).collect::<Vec<_>>(), backward);
+    assert_eq!(squares.iter_ordered(&REVERSED).rev().collect::<Vec<_>>(), forward);
+
+    let expected: Vec<_> = SCRAMBLED.by_rank().iter()
+        .map(|&i| Square(i))
+        .filter(|&s| squares.contains(s))
+        .collect();
+    let mut iter = squares.iter_ordered(&SCRAMBLED);
+    assert_eq!(iter.len(), expected.len());
+    assert_eq!(iter.next(), expected.first().cloned());
+    assert_eq!(iter.next_back(), expected.last().cloned());
+    assert_eq!(iter.len(), expected.len() - 2);
+    assert_eq!(iter.collect::<Vec<_>>(), &expected[1..expected.len() - 1]);
+
+    for (rank, &index) in SCRAMBLED.by_rank().iter().enumerate() {
+        assert_eq!(SCRAMBLED.rank(index as usize), rank);
+    }
+    assert_eq!(Squares::EMPTY.iter_ordered(&SCRAMBLED).next(), None);
+}
+
+#[test]
+#[should_panic]
+fn repeated_index() {
+    Order::new([0, 1, 1, 3]);
+}
+
+#[test]
+fn local_order() {
+    let mut by_rank = [0; 64];
+    for (i, x) in by_rank.iter_mut().enumerate() {
+        *x = 63 - i as u8;
+    }
+    let order = Order::new(by_rank);
+    assert!(Squares::FULL.iter_ordered(&order).eq(Squares::FULL.into_iter().rev()));
+}
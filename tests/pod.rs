@@ -0,0 +1,46 @@
+extern crate bit_collection;
+extern crate bytemuck;
+extern crate core;
+extern crate zerocopy;
+
+use core::marker::PhantomData;
+use bit_collection::*;
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Square(u8);
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Square, retr = "0", bytemuck)]
+#[repr(transparent)]
+struct Bitboard(u64, PhantomData<Square>);
+
+#[derive(BitCollection, FromBytes, IntoBytes, Immutable, KnownLayout)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[bit(Square, mask = "!0", retr = "0", zerocopy)]
+#[repr(transparent)]
+struct Squares(u32);
+
+#[test]
+fn bytemuck() {
+    let boards = [Bitboard::from(Square(1)), Bitboard::FULL];
+    let bytes: &[u8] = bytemuck::cast_slice(&boards);
+    assert_eq!(bytes.len(), 16);
+
+    let words: &[u64] = bytemuck::cast_slice(bytes);
+    assert_eq!(words, [2, !0]);
+
+    let read: &[Bitboard] = bytemuck::cast_slice(bytes);
+    assert_eq!(read, boards);
+    assert_eq!(<Bitboard as bytemuck::Zeroable>::zeroed(), Bitboard::EMPTY);
+}
+
+#[test]
+fn zerocopy() {
+    let squares = Squares::from(Square(0)) | Square(31);
+    let bytes = squares.as_bytes().to_vec();
+    assert_eq!(bytes, 0x8000_0001u32.to_ne_bytes());
+
+    let read = Squares::read_from_bytes(&bytes).unwrap();
+    assert_eq!(read.into_iter().collect::<Vec<_>>(), [Square(0), Square(31)]);
+}
@@ -0,0 +1,95 @@
+extern crate bit_collection;
+extern crate core;
+
+use bit_collection::BitCollection;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct File(u8);
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(File, retr = "0")]
+struct Files(u8);
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Rank(u8);
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Rank, retr = "0")]
+struct Ranks(u8);
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(product(Ranks, Files))]
+struct Squares(u64);
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Table(u8);
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Table, mask = "0b111", retr = "0")]
+struct Tables(u8);
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Seat(u8);
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Seat, mask = "0b1111", retr = "0")]
+struct Seats(u8);
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(product(Tables, Seats))]
+struct Assignments(u16);
+
+#[test]
+fn squares() {
+    assert_eq!(Squares::FULL.bits(), !0);
+    assert_eq!(Squares::FULL.len(), 64);
+
+    let square = (Rank(2), File(5));
+    let squares = Squares::from(square);
+    assert_eq!(squares.bits(), 1 << (2 * 8 + 5));
+    assert_eq!(squares.lsb(), Some(square));
+    assert_eq!(squares.msb(), Some(square));
+
+    let pairs: Vec<_> = Squares::FULL.into_iter().map(|(r, f)| (r.0, f.0)).collect();
+    let expected: Vec<_> = (0..8).flat_map(|r| (0..8).map(move |f| (r, f))).collect();
+    assert_eq!(pairs, expected);
+
+    let squares = Squares::from((Rank(0), File(0))) | (Rank(0), File(7)) | (Rank(6), File(7));
+    assert_eq!(squares.row(Rank(0)), Files::from(File(0)) | File(7));
+    assert_eq!(squares.row(Rank(1)), Files::EMPTY);
+    assert_eq!(squares.column(File(7)), Ranks::from(Rank(0)) | Rank(6));
+    assert_eq!(squares.column(File(3)), Ranks::EMPTY);
+
+    let rows = vec![
+        (Rank(0), Files::from(File(0)) | File(7)),
+        (Rank(6), Files::from(File(7))),
+    ];
+    assert_eq!(Squares::from_rows(rows), squares);
+    assert_eq!(Squares::from_rows(Ranks::FULL.into_iter().map(|r| (r, Files::FULL))),
+               Squares::FULL);
+}
+
+#[test]
+fn assignments() {
+    // Three rows of four seats
+    assert_eq!(Assignments::FULL.bits(), 0x0fff);
+    assert_eq!(Assignments::FULL.len(), 12);
+    assert_eq!(!Assignments::EMPTY, Assignments::FULL);
+
+    let taken = Assignments::from((Table(1), Seat(3))) | (Table(2), Seat(3)) | (Table(2), Seat(0));
+    assert_eq!(taken.column(Seat(3)), Tables::from(Table(1)) | Table(2));
+    assert_eq!(taken.row(Table(2)), Seats::from(Seat(0)) | Seat(3));
+
+    let pairs: Vec<_> = taken.into_iter().rev().map(|(t, s)| (t.0, s.0)).collect();
+    assert_eq!(pairs, [(2, 3), (2, 0), (1, 3)]);
+
+    for table in Tables::FULL {
+        for seat in Seats::FULL {
+            let single = Assignments::from((table, seat));
+            assert_eq!(single.row(table), Seats::from(seat));
+            assert_eq!(single.column(seat), Tables::from(table));
+            assert_eq!(Assignments::FULL.rank((table, seat)),
+                       table.0 as usize * 4 + seat.0 as usize);
+        }
+    }
+}
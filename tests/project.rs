@@ -0,0 +1,60 @@
+extern crate bit_collection;
+extern crate core;
+
+mod common;
+
+use bit_collection::*;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Square(u8);
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct File(u8);
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Rank(u8);
+
+fn square_file(s: Square) -> File { File(s.0 % 8) }
+
+fn square_rank(s: Square) -> Rank { Rank(s.0 / 8) }
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Square, retr = "0", project(Files = "square_file", Ranks = "square_rank"))]
+struct Squares(u64);
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(File, retr = "0")]
+struct Files(u8);
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Rank, mask = "0b0111_1111", retr = "0")]
+struct Ranks(u16);
+
+#[test]
+fn map_into() {
+    let squares = Squares::from(Square(9)) | Square(12) | Square(49);
+    let files: Files = squares.map_into(square_file);
+    let ranks: Ranks = squares.map_into(square_rank);
+    assert_eq!(files, Files::from(File(1)) | File(4));
+    assert_eq!(ranks, Ranks::from(Rank(1)) | Rank(6));
+    assert_eq!(Squares::EMPTY.map_into::<Files, _>(square_file), Files::EMPTY);
+
+    let squares: Squares = files.flat_map_into(|f| {
+        (0..8).map(|r| Square(r * 8 + f.0)).collect::<Squares>()
+    });
+    assert_eq!(squares.len(), 16);
+    assert_eq!(squares.map_into::<Files, _>(square_file), files);
+}
+
+#[test]
+fn project() {
+    for x in common::xorshift(0x9e37_79b9_7f4a_7c15).take(1000) {
+        // Squares of rank 7 map outside of the `Ranks` mask
+        let squares = Squares(x);
+        let files: Files = squares.project();
+        let ranks: Ranks = squares.project();
+        assert_eq!(files, squares.map_into(square_file));
+        assert_eq!(ranks, squares.map_into::<Ranks, _>(square_rank) & Ranks::FULL);
+        assert_eq!(ranks.bits() & !Ranks::FULL.bits(), 0);
+    }
+}
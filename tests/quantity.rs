@@ -3,10 +3,8 @@
 #[cfg(feature = "std")]
 extern crate core;
 
-#[macro_use]
 extern crate bit_collection;
 
-use core::u8;
 use bit_collection::*;
 
 #[derive(Copy, Clone)]
@@ -18,7 +16,7 @@ struct U8Bits(u8);
 
 #[test]
 fn quantity() {
-    for b in (0..u8::MAX).map(|x| U8Bits(x)) {
+    for b in (0..u8::MAX).map(U8Bits) {
         let q = match b.len() {
             0 => Quantity::None,
             1 => Quantity::Single,
@@ -27,3 +25,57 @@ fn quantity() {
         assert_eq!(q, b.quantity());
     }
 }
+
+#[derive(Copy, Clone)]
+struct U128Bit(u8);
+
+#[derive(BitCollection)]
+#[bit(U128Bit, retr = "0")]
+struct U128Bits(u128);
+
+#[test]
+fn len_cmp() {
+    for b in (0..u8::MAX).map(U8Bits) {
+        for n in 0..10 {
+            assert_eq!(b.len_cmp(n), b.len().cmp(&n));
+            assert_eq!(b.has_at_least(n), b.len() >= n);
+            assert_eq!(b.has_at_most(n), b.len() <= n);
+        }
+    }
+
+    let wide = U128Bits::from(U128Bit(127)) | U128Bit(64) | U128Bit(0);
+    assert!(wide.has_at_least(3) && !wide.has_at_least(4));
+    assert!(U128Bits::FULL.has_at_most(128) && U128Bits::FULL.has_at_least(128));
+}
+
+#[test]
+fn iter_quantity() {
+    for b in (0..u8::MAX).map(U8Bits) {
+        let q = b.quantity();
+        assert_eq!(IterQuantity::quantity(b.into_iter()), q);
+    }
+    assert_eq!(IterQuantity::quantity(0..), Quantity::Multiple);
+}
+
+#[test]
+fn quantity_item() {
+    for b in (0..u8::MAX).map(U8Bits) {
+        let item = b.quantity_item();
+        assert_eq!(item.quantity(), b.quantity());
+        assert_eq!(item.single().map(|x| x.0), b.into_bit().map(|x| x.0));
+    }
+
+    let mut sorted = [
+        QuantityItem::Multiple,
+        QuantityItem::Single(3),
+        QuantityItem::None,
+        QuantityItem::Single(1),
+    ];
+    sorted.sort_unstable();
+    assert_eq!(sorted, [
+        QuantityItem::None,
+        QuantityItem::Single(1),
+        QuantityItem::Single(3),
+        QuantityItem::Multiple,
+    ]);
+}
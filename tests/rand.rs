@@ -0,0 +1,62 @@
+#![cfg(feature = "rand")]
+
+extern crate bit_collection;
+extern crate core;
+extern crate rand;
+
+use bit_collection::*;
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct Move(u8);
+
+#[derive(BitCollection, Copy, Clone, Debug, Eq, PartialEq)]
+#[bit(Move, mask = "0x0ff0_0000_0000_00f5", retr = "0")]
+struct Moves(u64);
+
+#[test]
+fn choose() {
+    let mut rng = SmallRng::seed_from_u64(7);
+    assert_eq!(Moves::EMPTY.choose(&mut rng), None);
+
+    let moves = Moves::from(Move(0)) | Move(2) | Move(60);
+    let mut counts = [0; 3];
+    for _ in 0..3000 {
+        let m = moves.choose(&mut rng).unwrap();
+        counts[moves.rank(m)] += 1;
+    }
+    assert!(counts.iter().all(|&c| c > 900 && c < 1100), "{:?}", counts);
+}
+
+#[test]
+fn choose_multiple() {
+    let mut rng = SmallRng::seed_from_u64(7);
+    let moves = Moves::FULL;
+    for k in 0..moves.len() + 2 {
+        for _ in 0..20 {
+            let chosen = moves.choose_multiple(&mut rng, k);
+            assert_eq!(chosen.len(), k.min(moves.len()));
+            assert!(moves.contains(chosen));
+        }
+    }
+
+    let few = Moves::from(Move(4)) | Move(7);
+    assert_eq!(few.choose_multiple(&mut rng, 5), few);
+}
+
+#[test]
+fn random_subset() {
+    let mut rng = SmallRng::seed_from_u64(7);
+    assert_eq!(Moves::random_subset(&mut rng, 0.0), Moves::EMPTY);
+    assert_eq!(Moves::random_subset(&mut rng, 1.0), Moves::FULL);
+
+    let mut total = 0;
+    for _ in 0..1000 {
+        let subset = Moves::random_subset(&mut rng, 0.25);
+        assert!(Moves::FULL.contains(subset));
+        total += subset.len();
+    }
+    // 14 items with a 1 in 4 chance each
+    assert!(total > 3200 && total < 3800, "{}", total);
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "rayon")]
+
+extern crate bit_collection;
+extern crate core;
+extern crate rayon;
+
+use bit_collection::*;
+use rayon::prelude::*;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct Id(u8);
+
+#[derive(BitCollection, Copy, Clone)]
+#[bit(Id, retr = "0")]
+struct Ids(u128);
+
+#[test]
+fn visits_each_once() {
+    let ids = Ids(0xf0f0_0000_1234_5678_9abc_def0_0000_0001);
+
+    let par: Vec<Id> = ids.into_iter().into_par_iter().collect();
+    let seq: Vec<Id> = ids.into_iter().collect();
+    assert_eq!(par, seq);
+
+    let sum: u32 = ids.into_iter()
+        .into_par_iter()
+        .with_max_len(1)
+        .map(|id| id.0 as u32)
+        .sum();
+    assert_eq!(sum, seq.iter().map(|id| id.0 as u32).sum());
+
+    assert_eq!(Ids::EMPTY.into_iter().into_par_iter().count(), 0);
+    assert_eq!(Ids::FULL.into_iter().into_par_iter().len(), 128);
+}
+
+#[test]
+fn splits_exactly() {
+    let ids = Ids(!0 ^ 0b1010);
+    let (low, high) = ids.into_iter().into_par_iter().with_max_len(3).map(|id| {
+        if id.0 < 64 { (1u64 << id.0, 0) } else { (0, 1u64 << (id.0 - 64)) }
+    }).reduce(|| (0, 0), |a, b| {
+        assert_eq!(a.0 & b.0, 0);
+        assert_eq!(a.1 & b.1, 0);
+        (a.0 | b.0, a.1 | b.1)
+    });
+    assert_eq!((high as u128) << 64 | low as u128, ids.0);
+}
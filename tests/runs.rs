@@ -0,0 +1,75 @@
+extern crate bit_collection;
+extern crate core;
+
+use bit_collection::*;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Slot(u8);
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Slot, retr = "0")]
+struct Slots(u8);
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Slot, retr = "0")]
+struct WideSlots(u128);
+
+/// Returns the runs of `slots` by checking each bit.
+fn naive_runs(slots: Slots) -> Vec<Slots> {
+    let mut runs = Vec::new();
+    let mut run = Slots::EMPTY;
+    for i in 0..8 {
+        if slots.contains(Slot(i)) {
+            run |= Slot(i);
+        } else if !run.is_empty() {
+            runs.push(run);
+            run = Slots::EMPTY;
+        }
+    }
+    if !run.is_empty() {
+        runs.push(run);
+    }
+    runs
+}
+
+#[test]
+fn runs() {
+    for slots in (0..=u8::MAX).map(Slots) {
+        let expected = naive_runs(slots);
+        assert_eq!(slots.runs().len(), expected.len());
+        assert_eq!(slots.runs().collect::<Vec<_>>(), expected);
+
+        let mut rev: Vec<_> = slots.runs().rev().collect();
+        rev.reverse();
+        assert_eq!(rev, expected);
+
+        let longest = expected.iter().fold(Slots::EMPTY, |a, &b| {
+            if b.len() > a.len() { b } else { a }
+        });
+        assert_eq!(slots.longest_run(), longest);
+    }
+
+    let full: Vec<_> = WideSlots::FULL.runs().collect();
+    assert_eq!(full, [WideSlots::FULL]);
+    assert_eq!(WideSlots::FULL.runs().next_back(), Some(WideSlots::FULL));
+    assert_eq!(WideSlots::FULL.bounds().map(|(a, b)| (a.0, b.0)), Some((0, 127)));
+}
+
+#[test]
+fn first_run_of_len() {
+    for slots in (0..=u8::MAX).map(Slots) {
+        for n in 1..10 {
+            let expected = naive_runs(slots).into_iter()
+                .find(|run| run.len() >= n)
+                .map(|run| run.take_n(n).0);
+            assert_eq!(slots.first_run_of_len(n), expected);
+        }
+        assert_eq!(slots.first_run_of_len(0), Some(Slots::EMPTY));
+    }
+
+    let slots = WideSlots::FULL - Slot(3);
+    assert_eq!(slots.first_run_of_len(124).map(|r| r.len()), Some(124));
+    assert_eq!(slots.first_run_of_len(125), None);
+    assert_eq!(WideSlots::FULL.first_run_of_len(128), Some(WideSlots::FULL));
+    assert_eq!(WideSlots::FULL.first_run_of_len(129), None);
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "testing")]
+
+extern crate bit_collection;
+extern crate core;
+extern crate proptest;
+extern crate quickcheck;
+
+mod common;
+
+use bit_collection::*;
+use bit_collection::testing::{check_bit_collection_laws, strategy};
+use proptest::strategy::{Strategy, ValueTree};
+use proptest::test_runner::TestRunner;
+
+use common::{Square, Squares as Sparse};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Dir { North, East, South, West }
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Dir, mask = "0b1111")]
+struct Dirs(u8);
+
+#[derive(BitCollection, Copy, Clone, Debug, PartialEq)]
+#[bit(Square, retr = "0")]
+struct Wide(u128);
+
+#[test]
+fn laws() {
+    let dirs: Vec<Dir> = Dirs::FULL.into_iter().collect();
+    assert_eq!(dirs, [Dir::North, Dir::East, Dir::South, Dir::West]);
+
+    check_bit_collection_laws::<Dirs>();
+    check_bit_collection_laws::<Sparse>();
+    check_bit_collection_laws::<Wide>();
+}
+
+#[test]
+fn respects_full() {
+    let mut runner = TestRunner::deterministic();
+    for _ in 0..100 {
+        let tree = strategy::<Sparse>().new_tree(&mut runner).unwrap();
+        assert!(Sparse::FULL.contains(tree.current()));
+    }
+
+    let mut gen = quickcheck::Gen::new(100);
+    for _ in 0..100 {
+        let iter: BitIter<Sparse> = quickcheck::Arbitrary::arbitrary(&mut gen);
+        assert!(Sparse::FULL.contains(iter.0));
+        for smaller in quickcheck::Arbitrary::shrink(&iter) {
+            assert_eq!(smaller.len() + 1, iter.len());
+            assert!(iter.0.contains(smaller.0));
+        }
+    }
+}
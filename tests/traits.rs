@@ -0,0 +1,46 @@
+extern crate bit_collection;
+extern crate core;
+
+use std::collections::HashSet;
+use core::marker::PhantomData;
+use bit_collection::*;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Bit(u8);
+
+#[derive(BitCollection, Debug)]
+#[bit(Bit, retr = "0", traits(Default, Copy, Eq, Hash, Ord))]
+struct Bits(u8);
+
+// Not `Clone`, which the traits must not require
+struct Kind;
+
+#[derive(BitCollection)]
+#[bit(Bit, retr = "0", traits(Copy, PartialEq))]
+struct Marked(u16, PhantomData<Kind>);
+
+#[test]
+fn ord_matches_iteration() {
+    for a in 0..=u8::MAX {
+        for b in 0..=u8::MAX {
+            let (a, b) = (Bits(a), Bits(b));
+            assert_eq!(a.cmp(&b), a.into_iter().cmp(b.into_iter()), "{:?}, {:?}", a, b);
+        }
+    }
+
+    let mut sorted = [Bits(0b110), Bits(0b1), Bits(0), Bits(0b11), Bits(0b10)];
+    sorted.sort();
+    assert_eq!(sorted, [Bits(0), Bits(0b1), Bits(0b11), Bits(0b10), Bits(0b110)]);
+}
+
+#[test]
+fn others() {
+    assert_eq!(Bits::default(), Bits::EMPTY);
+
+    let set: HashSet<Bits> = [Bits(1), Bits(2), Bits(1)].iter().cloned().collect();
+    assert_eq!(set.len(), 2);
+
+    let marked = Marked::from(Bit(3));
+    let copy = marked;
+    assert!(marked == copy);
+}
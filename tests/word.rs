@@ -0,0 +1,96 @@
+extern crate bit_collection;
+extern crate core;
+
+mod common;
+
+use bit_collection::Word;
+use bit_collection::word::soft;
+
+/// Deterministic values with a mix of sparse and dense bit patterns.
+fn values() -> Vec<u64> {
+    let mut values = vec![0, 1, !0, 0x8000_0000_0000_0000, 0x00ff_00ff_00ff_00ff];
+    for state in common::xorshift(0x1234_5678_9abc_def0).take(500) {
+        values.push(state);
+        values.push(state & state.rotate_left(21));
+    }
+    values
+}
+
+fn bit<W: Word>(index: u32) -> W {
+    W::ONE << index
+}
+
+/// The indices of the bits set in `x`, found one bit at a time.
+fn indices<W: Word>(x: W) -> Vec<u32> {
+    (0..W::BITS).filter(|&i| x & bit(i) != W::ZERO).collect()
+}
+
+fn reference_select<W: Word>(x: W, n: u32) -> W {
+    indices(x).get(n as usize).map_or(W::ZERO, |&i| bit(i))
+}
+
+fn reference_pdep<W: Word>(x: W, mask: W) -> W {
+    indices(mask).into_iter().enumerate()
+        .filter(|&(k, _)| x & bit(k as u32) != W::ZERO)
+        .fold(W::ZERO, |acc, (_, i)| acc | bit(i))
+}
+
+fn reference_pext<W: Word>(x: W, mask: W) -> W {
+    indices(mask).into_iter().enumerate()
+        .filter(|&(_, i)| x & bit(i) != W::ZERO)
+        .fold(W::ZERO, |acc, (k, _)| acc | bit(k as u32))
+}
+
+fn check<W: Word>(x: W, y: W) {
+    let lowest = reference_select(x, 0);
+    let highest = indices(x).last().map_or(W::ZERO, |&i| bit(i));
+
+    assert_eq!(x.clear_lowest(), x ^ lowest);
+    assert_eq!(soft::clear_lowest(x), x ^ lowest);
+    assert_eq!(x.isolate_lowest(), lowest);
+    assert_eq!(soft::isolate_lowest(x), lowest);
+    assert_eq!(x.isolate_highest(), highest);
+
+    assert_eq!(x.pdep(y), reference_pdep(x, y));
+    assert_eq!(soft::pdep(x, y), reference_pdep(x, y));
+    assert_eq!(x.pext(y), reference_pext(x, y));
+    assert_eq!(soft::pext(x, y), reference_pext(x, y));
+
+    for n in 0..W::BITS + 1 {
+        let expected = reference_select(x, n);
+        assert_eq!(x.select(n), expected);
+        assert_eq!(soft::select(x, n), expected);
+    }
+}
+
+#[test]
+fn matches_reference() {
+    let values = values();
+    for (&x, &y) in values.iter().zip(values.iter().rev()) {
+        check(x as u8, y as u8);
+        check(x as u16, y as u16);
+        check(x as u32, y as u32);
+        check(x, y);
+        check(x as usize, y as usize);
+        check((x as u128) << 64 | y as u128, (y as u128) << 64 | x as u128);
+    }
+}
+
+#[test]
+fn select() {
+    let x = 0b1011_0100u8;
+    let bits: Vec<u8> = (0..5).map(|n| x.select(n)).collect();
+    assert_eq!(bits, [0b100, 0b1_0000, 0b10_0000, 0b1000_0000, 0]);
+
+    let x = 1u128 << 100 | 1 << 3;
+    assert_eq!(x.select(1), 1 << 100);
+    assert_eq!(x.isolate_highest(), 1 << 100);
+}
+
+#[test]
+fn deposit_extract() {
+    let mask = 0xf0f0u16;
+    assert_eq!(0b1010_0110u16.pdep(mask), 0b1010_0000_0110_0000);
+    assert_eq!(0b1010_0000_0110_0000u16.pext(mask), 0b1010_0110);
+    assert_eq!((!0u128).pext(1 << 127 | 1), 0b11);
+}
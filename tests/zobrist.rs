@@ -0,0 +1,40 @@
+extern crate bit_collection;
+extern crate core;
+
+use bit_collection::*;
+
+#[derive(Copy, Clone)]
+struct Square(u8);
+
+#[derive(BitCollection, Copy, Clone)]
+#[bit(Square, mask = "0b1010_1010", retr = "0")]
+struct Squares(u8);
+
+#[test]
+fn keys() {
+    let table = ZobristTable::<Squares>::new();
+    let keys: Vec<u64> = Squares::FULL.into_iter().map(|s| table.key(s)).collect();
+
+    assert_eq!(keys.len(), 4);
+    for (i, a) in keys.iter().enumerate() {
+        assert!(keys[i + 1..].iter().all(|b| a != b));
+    }
+
+    let other = ZobristTable::<Squares>::with_seed(1);
+    assert!(Squares::FULL.into_iter().all(|s| table.key(s) != other.key(s)));
+    assert_eq!(table.key(Square(3)), ZobristTable::<Squares>::new().key(Square(3)));
+}
+
+#[test]
+fn hash_update() {
+    let table = ZobristTable::<Squares>::new();
+    assert_eq!(table.hash(Squares::EMPTY), 0);
+
+    let mut squares = Squares::from(Square(1)) | Square(5);
+    let mut hash = table.hash(squares);
+    assert_eq!(hash, table.key(Square(1)) ^ table.key(Square(5)));
+
+    squares.remove(Square(1)).insert(Square(7));
+    hash = table.update(hash, Square(7), Square(1));
+    assert_eq!(hash, table.hash(squares));
+}